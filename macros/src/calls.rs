@@ -0,0 +1,253 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    parse_quote,
+    visit::Visit,
+    visit_mut::{self, VisitMut},
+    Block, Expr, ExprCall, ExprMethodCall, Macro, Path,
+};
+
+use crate::expand::box_path;
+
+/// Collects the names of the functions called from within a block.
+///
+/// Only calls that could resolve to an item in the same module or impl block are considered,
+/// i.e. calls to a bare name (`f(x)`), to a name qualified by `self` or `Self` (`self::f(x)`,
+/// `Self::f(x)`), or methods called on `self` (`self.f(x)`).  Macro invocations are opaque to
+/// us, so inside them any name followed by parenthesized arguments counts as a call.
+#[derive(Default)]
+pub struct CallVisitor {
+    pub called: HashSet<String>,
+}
+
+impl CallVisitor {
+    pub fn called_functions(block: &Block) -> HashSet<String> {
+        let mut v = CallVisitor::default();
+        v.visit_block(block);
+        v.called
+    }
+
+    fn visit_tokens(&mut self, tokens: TokenStream) {
+        let mut previous_ident = None;
+
+        for token in tokens {
+            match token {
+                TokenTree::Group(group) => {
+                    if group.delimiter() == Delimiter::Parenthesis {
+                        if let Some(ident) = previous_ident.take() {
+                            self.called.insert(ident);
+                        }
+                    }
+                    self.visit_tokens(group.stream());
+                }
+                TokenTree::Ident(ident) => previous_ident = Some(ident.to_string()),
+                _ => previous_ident = None,
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for CallVisitor {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        self.called.extend(call_target(expr));
+        syn::visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        self.visit_tokens(mac.tokens.clone());
+    }
+}
+
+/// Collects the names of the functions and methods that might be called from within a block, to
+/// tell whether a function could be recursive.
+///
+/// Unlike [`CallVisitor`] this errs on the side of finding calls: any path or method call counts,
+/// however it is qualified and whatever its receiver.  The names of the methods called on `self`
+/// or `Self` are also collected separately, since they may resolve to a method defined elsewhere
+/// (e.g. a trait's default method) that calls back into the function.  So are the names of the
+/// functions whose calls are awaited, as an async function defined elsewhere may do the same.
+#[derive(Default)]
+pub struct PossibleCallVisitor {
+    pub called: HashSet<String>,
+    pub called_on_self: HashSet<String>,
+    pub awaited: HashSet<String>,
+    /// Whether a future that doesn't come straight from a call is awaited, e.g. one stored in a
+    /// variable or awaited inside a macro invocation.
+    pub awaits_other: bool,
+}
+
+impl PossibleCallVisitor {
+    pub fn new(block: &Block) -> Self {
+        let mut v = PossibleCallVisitor::default();
+        v.visit_block(block);
+        v
+    }
+}
+
+impl<'ast> Visit<'ast> for PossibleCallVisitor {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Call(call) => {
+                if let Expr::Path(func) = &*call.func {
+                    if let Some(last) = func.path.segments.last() {
+                        self.called.insert(last.ident.to_string());
+                    }
+                }
+            }
+            Expr::MethodCall(call) => {
+                self.called.insert(call.method.to_string());
+            }
+            Expr::Await(future) => match &*future.base {
+                Expr::Call(ExprCall { func, .. }) => match &**func {
+                    Expr::Path(func) => self
+                        .awaited
+                        .extend(func.path.segments.last().map(|last| last.ident.to_string())),
+                    _ => self.awaits_other = true,
+                },
+                Expr::MethodCall(call) => {
+                    self.awaited.insert(call.method.to_string());
+                }
+                _ => self.awaits_other = true,
+            },
+            _ => {}
+        }
+
+        self.called_on_self.extend(self_call_target(expr));
+
+        syn::visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let mut calls = CallVisitor::default();
+        calls.visit_tokens(mac.tokens.clone());
+        self.called.extend(calls.called);
+        self.awaits_other |= mentions_await(mac.tokens.clone());
+    }
+}
+
+fn mentions_await(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Group(group) => mentions_await(group.stream()),
+        TokenTree::Ident(ident) => ident == "await",
+        _ => false,
+    })
+}
+
+/// Boxes every call to one of the given functions, so that they can recurse without boxing the
+/// futures they return (or without holding the inline storage they return).  Calls made inside
+/// macro invocations are left as they are.
+pub struct CallRewriter<'a> {
+    pub names: &'a HashSet<String>,
+    /// The function each call is passed to.
+    pub wrap: TokenStream,
+}
+
+impl<'a> CallRewriter<'a> {
+    pub fn boxing(names: &'a HashSet<String>, krate: &Path) -> Self {
+        let boxed = box_path(krate);
+        Self {
+            names,
+            wrap: quote!(#boxed::pin),
+        }
+    }
+}
+
+impl VisitMut for CallRewriter<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+
+        if call_target(expr).is_some_and(|name| self.names.contains(&name)) {
+            let call = std::mem::replace(expr, Expr::Verbatim(TokenStream::new()));
+            let wrap = &self.wrap;
+            *expr = parse_quote!(#wrap(#call));
+        }
+    }
+}
+
+/// Returns the name of the function being called if the given expression is a call to an item in
+/// the current module or impl block.
+fn call_target(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(func) if func.qself.is_none() => local_fn_name(&func.path),
+            _ => None,
+        },
+        Expr::MethodCall(call) => match &*call.receiver {
+            Expr::Path(receiver) if receiver.path.is_ident("self") => Some(call.method.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the name of the method being called if the given expression is a call to a method on
+/// `self` or `Self`.
+fn self_call_target(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(func) if func.qself.is_none() && func.path.segments.len() == 2 => {
+                let segments = &func.path.segments;
+                (segments[0].ident == "Self").then(|| segments[1].ident.to_string())
+            }
+            _ => None,
+        },
+        Expr::MethodCall(_) => call_target(expr),
+        _ => None,
+    }
+}
+
+/// Recognizes the calls a function makes to itself.
+pub struct SelfCalls<'a> {
+    pub name: &'a Ident,
+    /// Bare calls (`f(x)`) inside an impl block or trait refer to some other function.
+    pub associated: bool,
+    pub receiver: bool,
+}
+
+impl SelfCalls<'_> {
+    pub fn is_call(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Call(call) => self.path_call(call),
+            Expr::MethodCall(call) => self.method_call(call),
+            _ => false,
+        }
+    }
+
+    pub fn path_call(&self, call: &ExprCall) -> bool {
+        let path = match &*call.func {
+            Expr::Path(func) if func.qself.is_none() => &func.path,
+            _ => return false,
+        };
+
+        let segments: Vec<&Ident> = path.segments.iter().map(|segment| &segment.ident).collect();
+        match segments[..] {
+            [name] => !self.associated && name == self.name,
+            [first, name] if first == "self" => !self.associated && name == self.name,
+            [first, name] if first == "Self" => name == self.name,
+            _ => false,
+        }
+    }
+
+    pub fn method_call(&self, call: &ExprMethodCall) -> bool {
+        self.receiver && &call.method == self.name && is_self(&call.receiver)
+    }
+}
+
+pub fn is_self(expr: &Expr) -> bool {
+    matches!(expr, Expr::Path(path) if path.path.is_ident("self"))
+}
+
+fn local_fn_name(path: &Path) -> Option<String> {
+    let mut segments = path.segments.iter();
+    let first = segments.next()?;
+
+    match (segments.next(), segments.next()) {
+        (None, _) => Some(first.ident.to_string()),
+        (Some(second), None) if first.ident == "self" || first.ident == "Self" => {
+            Some(second.ident.to_string())
+        }
+        _ => None,
+    }
+}
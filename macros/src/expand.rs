@@ -0,0 +1,1073 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Error, Result},
+    parse_quote, parse_quote_spanned,
+    punctuated::Punctuated,
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    Attribute, Block, FnArg, Generics, ImplItem, Item, ItemImpl, ItemMod, ItemTrait, Lifetime,
+    ParenthesizedGenericArguments, Path, PathArguments, PathSegment, Receiver, ReturnType,
+    Signature, TraitBound, TraitItem, Type, TypeBareFn, TypeImplTrait, TypeReference, WhereClause,
+};
+
+use crate::{
+    calls::{CallRewriter, CallVisitor, PossibleCallVisitor},
+    graph::CallGraph,
+    memoize::{memo_key, MemoKey},
+    parse::{is_async_recursion_attr, parse_attr_args, AsyncItem, RecursionArgs},
+    static_pool::{inner_fn, InnerFn},
+    tail::eliminate_tail_calls,
+    unroll::unroll,
+};
+
+impl ToTokens for AsyncItem {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            AsyncItem::Fn(item) => item.to_tokens(tokens),
+            AsyncItem::Impl(item) => item.to_tokens(tokens),
+            AsyncItem::Trait(item) => item.to_tokens(tokens),
+            AsyncItem::TraitFn(item) => item.to_tokens(tokens),
+            AsyncItem::Mod(item) => item.to_tokens(tokens),
+        }
+    }
+}
+
+pub fn expand(item: &mut AsyncItem, args: &RecursionArgs) -> Result<()> {
+    match item {
+        AsyncItem::Fn(item) => {
+            // This may still be a method with its own attribute inside an impl block
+            let associated = item.sig.receiver().is_some();
+            let f = AsyncFn {
+                attrs: &mut item.attrs,
+                sig: &mut item.sig,
+                block: Some(&mut item.block),
+                in_trait: false,
+                associated,
+                outer_lifetimes: HashSet::new(),
+                trait_method: false,
+            };
+            expand_fns(vec![(f, Some(args))], &args.krate)
+        }
+        AsyncItem::Impl(item) => expand_impl(item, args),
+        AsyncItem::Trait(item) => expand_trait(item, args),
+        AsyncItem::TraitFn(item) => {
+            let f = AsyncFn {
+                attrs: &mut item.attrs,
+                sig: &mut item.sig,
+                block: item.default.as_mut(),
+                in_trait: true,
+                associated: true,
+                outer_lifetimes: HashSet::new(),
+                trait_method: true,
+            };
+            expand_fns(vec![(f, Some(args))], &args.krate)
+        }
+        AsyncItem::Mod(item) => expand_mod(item, args),
+    }
+}
+
+/// An async function, method or method declaration to be rewritten.
+struct AsyncFn<'a> {
+    attrs: &'a mut Vec<Attribute>,
+    sig: &'a mut Signature,
+    block: Option<&'a mut Block>,
+    in_trait: bool,
+    /// Whether the function is defined in an impl block or trait, rather than a module.
+    associated: bool,
+    /// The lifetimes declared by the enclosing impl block or trait.
+    outer_lifetimes: HashSet<String>,
+    /// Whether the function is declared by a trait or implements one, so that its signature is
+    /// fixed by the trait rather than by whether it recurses.
+    trait_method: bool,
+}
+
+// Rewrites a group of async functions that may call each other, such as the methods of an impl
+// block.  Functions without arguments are left as they are, apart from boxing any calls they make
+// to functions in the group that are boxed at the call site.
+fn expand_fns(fns: Vec<(AsyncFn<'_>, Option<&RecursionArgs>)>, krate: &Path) -> Result<()> {
+    let recursive = possibly_recursive(&fns);
+
+    let call_site: HashSet<String> = fns
+        .iter()
+        .filter(|(_, args)| args.is_some_and(|args| args.call_site))
+        .map(|(f, _)| f.sig.ident.to_string())
+        .collect();
+
+    let (inline, unboxed) = inline_calls(&fns);
+
+    for ((mut f, args), recursive) in fns.into_iter().zip(recursive) {
+        if let (Some(block), Some(args)) = (&mut f.block, args) {
+            if !recursive && !args.allow_nonrecursive && !f.trait_method {
+                warn_nonrecursive(&f.sig.ident, block);
+            }
+        }
+
+        if !call_site.is_empty() {
+            if let Some(block) = &mut f.block {
+                CallRewriter::boxing(&call_site, krate).visit_block_mut(block);
+            }
+        }
+
+        if let (
+            Some(block),
+            Some(RecursionArgs {
+                inline: Some(_), ..
+            }),
+        ) = (&mut f.block, args)
+        {
+            CallRewriter {
+                names: &inline,
+                wrap: quote!(#krate::Inline::into_boxed),
+            }
+            .visit_block_mut(block);
+            CallRewriter::boxing(&unboxed, krate).visit_block_mut(block);
+        }
+
+        match args {
+            Some(args) if args.call_site => {}
+            Some(RecursionArgs {
+                unroll: Some(levels),
+                no_alloc,
+                ..
+            }) => {
+                if let Some(block) = f.block {
+                    unroll(f.sig, block, f.associated, *levels, *no_alloc, krate)?;
+                }
+            }
+            Some(args) => expand_fn(f, args)?,
+            None => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Works out which functions in a group might end up calling themselves.  Calls into code we can't
+// see, such as a method declaration without a body, a method on `self` defined outside of the
+// group or an awaited function defined outside of it, are assumed to call back into every
+// function in the group.
+fn possibly_recursive(fns: &[(AsyncFn<'_>, Option<&RecursionArgs>)]) -> Vec<bool> {
+    let names: Vec<String> = fns.iter().map(|(f, _)| f.sig.ident.to_string()).collect();
+    let mut graph = CallGraph::new(fns.len());
+
+    for (from, (f, _)) in fns.iter().enumerate() {
+        let calls_unknown = match &f.block {
+            Some(block) => {
+                let calls = PossibleCallVisitor::new(block);
+                for (to, name) in names.iter().enumerate() {
+                    if calls.called.contains(name) {
+                        graph.add_edge(from, to);
+                    }
+                }
+                calls.awaits_other
+                    || calls
+                        .called_on_self
+                        .iter()
+                        .chain(&calls.awaited)
+                        .any(|name| !names.contains(name))
+            }
+            None => true,
+        };
+
+        if calls_unknown {
+            for to in 0..fns.len() {
+                graph.add_edge(from, to);
+            }
+        }
+    }
+
+    graph.in_cycle()
+}
+
+// A future stored inline can't hold the storage of a call that leads back to itself, as that
+// storage would have to be larger than itself.  So inlined functions box the calls they make to
+// other inlined functions in the group, as well as to the functions that aren't boxed at all (in
+// a module) but lead to one, leaving only the calls made from outside of the recursion inline.
+fn inline_calls(
+    fns: &[(AsyncFn<'_>, Option<&RecursionArgs>)],
+) -> (HashSet<String>, HashSet<String>) {
+    let names: Vec<String> = fns.iter().map(|(f, _)| f.sig.ident.to_string()).collect();
+    let inlined: Vec<bool> = fns
+        .iter()
+        .map(|(_, args)| args.is_some_and(|args| args.inline.is_some()))
+        .collect();
+    if !inlined.contains(&true) {
+        return Default::default();
+    }
+
+    let mut graph = CallGraph::new(fns.len());
+    for (from, (f, _)) in fns.iter().enumerate() {
+        if let Some(block) = &f.block {
+            let called = CallVisitor::called_functions(block);
+            for (to, name) in names.iter().enumerate() {
+                if called.contains(name) {
+                    graph.add_edge(from, to);
+                }
+            }
+        }
+    }
+
+    let reaching = graph.reaching(&inlined);
+    let mut inline = HashSet::new();
+    let mut unboxed = HashSet::new();
+    for (i, (name, (_, args))) in names.into_iter().zip(fns).enumerate() {
+        if inlined[i] {
+            inline.insert(name);
+        } else if reaching[i] && args.is_none() {
+            unboxed.insert(name);
+        }
+    }
+
+    (inline, unboxed)
+}
+
+fn expand_fn(mut f: AsyncFn<'_>, args: &RecursionArgs) -> Result<()> {
+    f.attrs.push(parse_quote!(#[must_use]));
+
+    if args.tail {
+        if let Some(block) = &mut f.block {
+            eliminate_tail_calls(block, f.sig, f.associated)?;
+        }
+    }
+
+    // Checking the depth depends on the original return type, so this must be done before the
+    // signature is rewritten
+    if let (Some(block), Some(max_depth)) = (&mut f.block, args.max_depth) {
+        check_depth(block, f.sig, max_depth, &args.krate);
+    }
+
+    if args.trampoline && f.block.is_some() {
+        check_static_args(f.sig)?;
+    }
+
+    if args.fallible && !returns_result(&f.sig.output) {
+        return Err(Error::new_spanned(
+            &f.sig.ident,
+            "`fallible` requires the function to return a `Result`",
+        ));
+    }
+
+    if args.detect_cycles.is_some() {
+        if !returns_result(&f.sig.output) {
+            return Err(Error::new_spanned(
+                &f.sig.ident,
+                "`detect_cycles` requires the function to return a `Result`",
+            ));
+        }
+        if let Some(block) = &mut f.block {
+            check_cycles(block);
+        }
+    }
+
+    // The inner function takes the arguments as they were, before any bounds are added
+    let inner = match (&f.block, args.static_pool) {
+        (Some(_), Some(_)) => Some(inner_fn(f.sig, returns_result(&f.sig.output))?),
+        _ => None,
+    };
+
+    let memo = match (&f.block, args.memoize) {
+        (Some(_), Some(_)) => Some(memo_key(f.sig, f.associated)?),
+        _ => None,
+    };
+
+    let future_ty = transform_sig(f.sig, args, &f.outer_lifetimes);
+
+    if let Some(block) = f.block {
+        let memo = memo.map(|memo| (memo, future_ty));
+        transform_block(block, args, inner, memo);
+
+        if f.in_trait && args.send_bound {
+            add_default_method_bound(f.sig);
+        }
+    }
+
+    Ok(())
+}
+
+// Trampolined calls are polled once their caller's `poll` has returned, so they can't borrow
+// anything from it.
+fn check_static_args(sig: &Signature) -> Result<()> {
+    for input in &sig.inputs {
+        let mut borrows = FindBorrows(false);
+        match input {
+            FnArg::Receiver(receiver) => borrows.visit_type(&receiver.ty),
+            FnArg::Typed(input) => borrows.visit_type(&input.ty),
+        }
+        if borrows.0 {
+            return Err(Error::new_spanned(
+                input,
+                "`trampoline` requires every argument to be `'static`",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds any reference or lifetime other than `'static`, which ties a type to its caller.
+pub struct FindBorrows(pub bool);
+
+impl Visit<'_> for FindBorrows {
+    fn visit_type_reference(&mut self, reference: &TypeReference) {
+        if reference.lifetime.is_none() {
+            self.0 = true;
+        }
+        visit::visit_type_reference(self, reference);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &Lifetime) {
+        if lifetime.ident != "static" {
+            self.0 = true;
+        }
+    }
+}
+
+// Stable proc macros can't emit warnings, so instead we use an item marked as deprecated to have
+// the compiler warn on our behalf.
+fn warn_nonrecursive(ident: &Ident, block: &mut Block) {
+    let note = format!(
+        "`{ident}` never calls itself, so `#[async_recursion]` only adds an allocation per call; \
+         remove the attribute, or pass `allow_nonrecursive` to silence this warning"
+    );
+
+    block.stmts.insert(
+        0,
+        parse_quote_spanned! {ident.span()=>
+            {
+                #[deprecated(note = #note)]
+                #[allow(non_camel_case_types)]
+                struct async_recursion_nonrecursive;
+                let _ = async_recursion_nonrecursive;
+            }
+        },
+    );
+}
+
+// Rewrites every async method in an impl block, leaving all other items untouched.
+fn expand_impl(item: &mut ItemImpl, args: &RecursionArgs) -> Result<()> {
+    let outer_lifetimes = lifetime_names(&item.generics);
+    let trait_method = item.trait_.is_some();
+    let mut fns = vec![];
+    let mut fn_args = vec![];
+
+    for impl_item in &mut item.items {
+        if let ImplItem::Fn(method) = impl_item {
+            let inner_args = inner_args(&mut method.attrs, &method.sig)?;
+
+            if method.sig.asyncness.is_some() {
+                fn_args.push(inner_args);
+                fns.push(AsyncFn {
+                    attrs: &mut method.attrs,
+                    sig: &mut method.sig,
+                    block: Some(&mut method.block),
+                    in_trait: false,
+                    associated: true,
+                    outer_lifetimes: outer_lifetimes.clone(),
+                    trait_method,
+                });
+            }
+        }
+    }
+
+    let fn_args = fn_args
+        .iter()
+        .map(|inner| Some(inner.as_ref().unwrap_or(args)));
+    expand_fns(fns.into_iter().zip(fn_args).collect(), &args.krate)
+}
+
+// Rewrites every async method declaration and default method in a trait definition, leaving all
+// other items untouched.
+fn expand_trait(item: &mut ItemTrait, args: &RecursionArgs) -> Result<()> {
+    let outer_lifetimes = lifetime_names(&item.generics);
+    let mut fns = vec![];
+    let mut fn_args = vec![];
+
+    for trait_item in &mut item.items {
+        if let TraitItem::Fn(method) = trait_item {
+            let inner_args = inner_args(&mut method.attrs, &method.sig)?;
+
+            if method.sig.asyncness.is_some() {
+                fn_args.push(inner_args);
+                fns.push(AsyncFn {
+                    attrs: &mut method.attrs,
+                    sig: &mut method.sig,
+                    block: method.default.as_mut(),
+                    in_trait: true,
+                    associated: true,
+                    outer_lifetimes: outer_lifetimes.clone(),
+                    trait_method: true,
+                });
+            }
+        }
+    }
+
+    let fn_args = fn_args
+        .iter()
+        .map(|inner| Some(inner.as_ref().unwrap_or(args)));
+    expand_fns(fns.into_iter().zip(fn_args).collect(), &args.krate)
+}
+
+// Breaks every cycle of calls between the async functions in a module by rewriting a minimal set
+// of them, leaving all other items untouched.  Functions carrying their own attribute are always
+// rewritten.
+fn expand_mod(item: &mut ItemMod, args: &RecursionArgs) -> Result<()> {
+    let items = match &mut item.content {
+        Some((_, items)) => items,
+        None => return Ok(()),
+    };
+
+    let mut fns = vec![];
+    let mut fn_args = vec![];
+    let mut graph_fns = vec![];
+
+    for item in items {
+        if let Item::Fn(f) = item {
+            if f.sig.asyncness.is_some() {
+                fn_args.push(inner_args(&mut f.attrs, &f.sig)?);
+                graph_fns.push((
+                    f.sig.ident.to_string(),
+                    CallVisitor::called_functions(&f.block),
+                ));
+                fns.push(AsyncFn {
+                    attrs: &mut f.attrs,
+                    sig: &mut f.sig,
+                    block: Some(&mut f.block),
+                    in_trait: false,
+                    associated: false,
+                    outer_lifetimes: HashSet::new(),
+                    trait_method: false,
+                });
+            }
+        }
+    }
+
+    let mut graph = CallGraph::new(graph_fns.len());
+    // The same calls, leaving out those a function makes to itself
+    let mut mutual = CallGraph::new(graph_fns.len());
+    for (from, (_, called)) in graph_fns.iter().enumerate() {
+        for called in called {
+            if let Some(to) = graph_fns.iter().position(|(name, _)| name == called) {
+                graph.add_edge(from, to);
+                if from != to {
+                    mutual.add_edge(from, to);
+                }
+            }
+        }
+    }
+
+    let explicit: Vec<bool> = fn_args.iter().map(Option::is_some).collect();
+    let boxed = graph.break_cycles(&explicit);
+
+    let fn_args: Vec<Option<&RecursionArgs>> = fn_args
+        .iter()
+        .zip(boxed)
+        .map(|(inner, boxed)| boxed.then_some(inner.as_ref().unwrap_or(args)))
+        .collect();
+
+    // Only the calls a function makes to itself are unrolled, so the calls going around a longer
+    // cycle would still make its future contain itself
+    for ((f, args), mutual) in fns.iter().zip(&fn_args).zip(mutual.in_cycle()) {
+        if mutual && args.is_some_and(|args| args.unroll.is_some()) {
+            return Err(Error::new_spanned(
+                &f.sig.ident,
+                "`unroll` can't be used on functions that call each other, only on a function \
+                 that calls itself",
+            ));
+        }
+    }
+
+    expand_fns(fns.into_iter().zip(fn_args).collect(), &args.krate)
+}
+
+// A default method body captures `self` inside a `Send` future, so we need to require the
+// corresponding bound on `Self`.  Implementations don't need this bound as they know the concrete
+// type of `self`, and may omit it without changing their signature.
+fn add_default_method_bound(sig: &mut Signature) {
+    let self_bound: Option<TokenStream> = match sig.receiver() {
+        Some(Receiver {
+            reference: Some(_),
+            mutability: None,
+            ..
+        }) => Some(quote!(Self : ::core::marker::Sync)),
+        // For typed receivers (e.g. self: Arc<Self>) it's the receiver type itself that must
+        // be Send
+        Some(Receiver {
+            colon_token: Some(_),
+            ty,
+            ..
+        }) => Some(quote!(#ty : ::core::marker::Send)),
+        Some(_) => Some(quote!(Self : ::core::marker::Send)),
+        None => None,
+    };
+
+    if let Some(self_bound) = self_bound {
+        sig.generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#self_bound));
+    }
+}
+
+// A function inside an impl block, trait or module may carry its own `#[async_recursion(...)]`
+// attribute, in which case those arguments are used instead of the ones given to the enclosing
+// item.
+fn inner_args(attrs: &mut Vec<Attribute>, sig: &Signature) -> Result<Option<RecursionArgs>> {
+    let pos = match attrs.iter().position(is_async_recursion_attr) {
+        Some(pos) => pos,
+        None => return Ok(None),
+    };
+
+    let attr = attrs.remove(pos);
+    if sig.asyncness.is_none() {
+        return Err(Error::new_spanned(attr, "expected an async function"));
+    }
+
+    parse_attr_args(&attr).map(Some)
+}
+
+/// The path to `Box` as re-exported by the `async-recursion` crate, which keeps working when the
+/// user shadows `Box`, has no prelude at all or is in a `#![no_std]` crate.
+pub fn box_path(krate: &Path) -> TokenStream {
+    quote!(#krate::__private::Box)
+}
+
+// The identifiers used to track the depth of recursion, which are hidden from the user's code.
+fn depth_idents() -> (Ident, Ident) {
+    (
+        Ident::new("__ASYNC_RECURSION_KEY", Span::mixed_site()),
+        Ident::new("__async_recursion_depth", Span::mixed_site()),
+    )
+}
+
+// Bails out at the start of the function body once the maximum depth is exceeded, either by
+// returning an error if the function returns a `Result<T, E>`, or otherwise by panicking.
+fn check_depth(block: &mut Block, sig: &Signature, max_depth: usize, krate: &Path) {
+    let (_, depth) = depth_idents();
+    let name = sig.ident.to_string();
+
+    let bail = if returns_result(&sig.output) {
+        quote! {
+            return ::core::result::Result::Err(::core::convert::From::from(
+                #krate::__private::depth::exceeded(#max_depth),
+            ));
+        }
+    } else {
+        quote!(#krate::__private::depth::panic_exceeded(#name, #max_depth);)
+    };
+
+    block.stmts.insert(
+        0,
+        parse_quote! {
+            if #depth > #max_depth {
+                #bail
+            }
+        },
+    );
+}
+
+// The identifiers used to detect cycles, which are hidden from the user's code.
+fn cycle_idents() -> (Ident, Ident, Ident) {
+    (
+        Ident::new("__ASYNC_RECURSION_CYCLES", Span::mixed_site()),
+        Ident::new("__async_recursion_key", Span::mixed_site()),
+        Ident::new("__async_recursion_cycle", Span::mixed_site()),
+    )
+}
+
+// Returns the error at the start of the function body if the call was found to close a cycle.
+fn check_cycles(block: &mut Block) {
+    let (_, _, cycle) = cycle_idents();
+
+    block.stmts.insert(
+        0,
+        parse_quote! {
+            if let ::core::option::Option::Some(#cycle) = #cycle {
+                return ::core::result::Result::Err(::core::convert::From::from(#cycle));
+            }
+        },
+    );
+}
+
+// Returns true if the return type is written as `Result<T, E>`.
+pub fn returns_result(output: &ReturnType) -> bool {
+    let path = match output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(ty) if ty.qself.is_none() => &ty.path,
+            _ => return false,
+        },
+        ReturnType::Default => return false,
+    };
+
+    match path.segments.last() {
+        Some(PathSegment {
+            ident,
+            arguments: PathArguments::AngleBracketed(args),
+        }) => ident == "Result" && args.args.len() == 2,
+        _ => false,
+    }
+}
+
+fn transform_block(
+    block: &mut Block,
+    args: &RecursionArgs,
+    inner: Option<InnerFn>,
+    memo: Option<(MemoKey, TokenStream)>,
+) {
+    let brace = block.brace_token;
+    let krate = &args.krate;
+    let boxed = box_path(krate);
+
+    let mut setup = TokenStream::new();
+    let mut future = quote!(async move #block);
+
+    if args.max_depth.is_some() {
+        // The depth is worked out when the future is created, i.e. while the caller is being
+        // polled, and is made known to any calls made while this future is being polled
+        let (key, depth) = depth_idents();
+        setup.extend(quote! {
+            static #key: u8 = 0;
+            let #depth = #krate::__private::depth::child_depth(&#key);
+        });
+        future = quote!(#krate::__private::depth::WithDepth::new(&#key, #depth, #future));
+    }
+
+    if let Some(key_expr) = &args.detect_cycles {
+        // Likewise, the chain of calls leading to this one is looked up when the future is
+        // created, and the chain ending with this call is made known to the calls it makes
+        let (fn_key, key, cycle) = cycle_idents();
+        setup.extend(quote! {
+            static #fn_key: u8 = 0;
+            let (#key, #cycle) = #krate::__private::cycles::find_cycle(&#fn_key, #key_expr);
+        });
+        future = quote!(#krate::__private::cycles::WithCycleKey::new(&#fn_key, #key, #future));
+    }
+
+    if args.grow_stack {
+        future = quote!(#krate::__private::grow_stack::GrowStack::new(#future));
+    }
+
+    // The trampoline goes on the outside, so that whatever else wraps the body is polled by the
+    // driver along with it
+    if args.trampoline {
+        future = quote!(#krate::__private::trampoline::Trampoline::new(#future));
+    }
+
+    *block = if args.pool {
+        // Every function gets its own pool, as blocks are only reused for futures of the same size
+        let pool = Ident::new("__ASYNC_RECURSION_POOL", Span::mixed_site());
+        parse_quote!({
+            #setup
+            #krate::__private::pool::thread_local! {
+                static #pool: #krate::__private::pool::Pool = const {
+                    #krate::__private::pool::Pool::new()
+                };
+            }
+            #krate::Pooled::new(&#pool, #future)
+        })
+    } else if args.inline.is_some() {
+        parse_quote!({
+            #setup
+            #krate::Inline::new(#future)
+        })
+    } else if let Some(boxer) = &args.boxer {
+        parse_quote!({
+            #setup
+            <#boxer as #krate::Boxer>::pin(#future)
+        })
+    } else if let (Some(capacity), Some(inner)) = (args.static_pool, inner) {
+        // Anything wrapping the body is set up by the inner function
+        let wrapped = args.max_depth.is_some()
+            || args.detect_cycles.is_some()
+            || args.grow_stack
+            || args.trampoline;
+        let body = if wrapped {
+            quote!({ #setup #future.await })
+        } else {
+            quote!(#block)
+        };
+        let inner = inner.expand(body, capacity, krate);
+        parse_quote!({ #inner })
+    } else if args.fallible {
+        parse_quote!({
+            #setup
+            #krate::__private::fallible::try_pin(#future)
+        })
+    } else if let (Some(memoize), Some((memo, future_ty))) = (args.memoize, memo) {
+        let memo = memo.expand(future, future_ty, memoize, krate);
+        parse_quote!({
+            #setup
+            #memo
+        })
+    } else {
+        parse_quote!({
+            #setup
+            #boxed::pin(#future)
+        })
+    };
+    block.brace_token = brace;
+}
+
+enum ArgLifetime {
+    New(Lifetime),
+    Existing(Lifetime),
+}
+
+impl ArgLifetime {
+    pub fn lifetime(self) -> Lifetime {
+        match self {
+            ArgLifetime::New(lt) | ArgLifetime::Existing(lt) => lt,
+        }
+    }
+}
+
+// Collects the names of every lifetime mentioned in a signature, so that the lifetimes we generate
+// don't collide with them.
+#[derive(Default)]
+struct LifetimeNames(HashSet<String>);
+
+impl<'ast> Visit<'ast> for LifetimeNames {
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        self.0.insert(lifetime.ident.to_string());
+    }
+}
+
+fn lifetime_names(generics: &Generics) -> HashSet<String> {
+    generics
+        .lifetimes()
+        .map(|param| param.lifetime.ident.to_string())
+        .collect()
+}
+
+// Returns a lifetime named `name`, or `name` followed by a number if that is already taken.
+fn unique_lifetime(name: &str, taken: &HashSet<String>) -> Lifetime {
+    let ident = std::iter::once(name.to_string())
+        .chain((0..).map(|i| format!("{name}{i}")))
+        .find(|ident| !taken.contains(ident))
+        .expect("there are infinitely many names");
+
+    Lifetime::new(&format!("'{ident}"), Span::call_site())
+}
+
+struct ReferenceVisitor {
+    taken: HashSet<String>,
+    asr: Lifetime,
+    life_self: Lifetime,
+    counter: usize,
+    lifetimes: Vec<ArgLifetime>,
+    self_receiver: bool,
+    self_receiver_new_lifetime: bool,
+    self_lifetime: Option<Lifetime>,
+    in_typed_receiver: bool,
+    impl_trait: bool,
+}
+
+impl ReferenceVisitor {
+    fn new(taken: HashSet<String>, asr: Lifetime) -> Self {
+        Self {
+            life_self: unique_lifetime("__async_recursion_life_self", &taken),
+            taken,
+            asr,
+            counter: 0,
+            lifetimes: vec![],
+            self_receiver: false,
+            self_receiver_new_lifetime: false,
+            self_lifetime: None,
+            in_typed_receiver: false,
+            impl_trait: false,
+        }
+    }
+
+    // The lifetimes given to elided references are prefixed, as the function may be inside an impl
+    // block declaring lifetimes that we can't see (e.g. `impl<'life0> Tree<'life0>`).
+    fn new_lifetime(&mut self) -> Lifetime {
+        // Skip over any such lifetimes already used by the function
+        let lt = loop {
+            let ident = format!("__async_recursion_life{}", self.counter);
+            self.counter += 1;
+            if !self.taken.contains(&ident) {
+                break Lifetime::new(&format!("'{ident}"), Span::call_site());
+            }
+        };
+
+        self.lifetimes.push(ArgLifetime::New(lt.clone()));
+        lt
+    }
+}
+
+impl VisitMut for ReferenceVisitor {
+    fn visit_receiver_mut(&mut self, receiver: &mut Receiver) {
+        if receiver.colon_token.is_some() {
+            // This is a typed receiver (e.g. self: Pin<&mut Self>), so look for references
+            // inside of its type.
+            self.in_typed_receiver = true;
+            self.visit_type_mut(&mut receiver.ty);
+            self.in_typed_receiver = false;
+            return;
+        }
+
+        self.self_lifetime = Some(if let Some((_, lt)) = &mut receiver.reference {
+            self.self_receiver = true;
+
+            if lt.as_ref().is_some_and(|lt| lt.ident == "_") {
+                *lt = None;
+            }
+
+            if let Some(lt) = lt {
+                lt.clone()
+            } else {
+                // Use 'life_self to avoid collisions with 'life<count> lifetimes.
+                let new_lifetime = self.life_self.clone();
+                lt.replace(new_lifetime.clone());
+
+                self.self_receiver_new_lifetime = true;
+
+                new_lifetime
+            }
+        } else {
+            return;
+        });
+    }
+
+    fn visit_type_reference_mut(&mut self, argument: &mut TypeReference) {
+        // An explicitly elided lifetime (e.g. &'_ T) is the same as no lifetime at all
+        if argument.lifetime.as_ref().is_some_and(|lt| lt.ident == "_") {
+            argument.lifetime = None;
+        }
+
+        if argument.lifetime.is_none() && self.in_typed_receiver && self.self_lifetime.is_none() {
+            // The first elided reference in a typed receiver (e.g. self: &Arc<Self>) is treated
+            // in the same way as &self.
+            let new_lifetime = self.life_self.clone();
+            argument.lifetime = Some(new_lifetime.clone());
+
+            self.self_receiver = true;
+            self.self_receiver_new_lifetime = true;
+            self.self_lifetime = Some(new_lifetime);
+        } else if argument.lifetime.is_none() {
+            // If this reference doesn't have a lifetime (e.g. &T), then give it one.
+            argument.lifetime = Some(self.new_lifetime());
+        } else {
+            // If it does (e.g. &'life T), then keep track of it.
+            let lt = argument.lifetime.as_ref().cloned().unwrap();
+
+            // Check that this lifetime isn't already in our vector
+            let ident_matches = |x: &ArgLifetime| {
+                if let ArgLifetime::Existing(elt) = x {
+                    elt.ident == lt.ident
+                } else {
+                    false
+                }
+            };
+
+            if !self.lifetimes.iter().any(ident_matches) {
+                self.lifetimes.push(ArgLifetime::Existing(lt));
+            }
+        }
+
+        // Look for references inside of the referenced type (e.g. &Vec<&T>)
+        visit_mut::visit_type_reference_mut(self, argument);
+    }
+
+    // Any other '_ (e.g. Cursor<'_> or Box<dyn Trait + '_>) also gets a lifetime of its own.
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.new_lifetime();
+        }
+    }
+
+    // Elided lifetimes inside a function pointer (e.g. fn(&str) -> &str), `Fn` trait sugar (e.g.
+    // dyn Fn(&T)) or a `for<'a>` binder are higher-ranked, so they must be left alone.
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut ParenthesizedGenericArguments) {
+    }
+
+    // The anonymous type parameter introduced by `impl Trait` (e.g. visit: impl FnMut(&Node)) is
+    // captured by the future just like a named one, so it needs the same 'async_recursion bound.
+    fn visit_type_impl_trait_mut(&mut self, impl_trait: &mut TypeImplTrait) {
+        visit_mut::visit_type_impl_trait_mut(self, impl_trait);
+
+        let asr = &self.asr;
+        impl_trait.bounds.push(parse_quote!(#asr));
+        self.impl_trait = true;
+    }
+
+    fn visit_trait_bound_mut(&mut self, bound: &mut TraitBound) {
+        if bound.lifetimes.is_none() {
+            visit_mut::visit_trait_bound_mut(self, bound);
+        }
+    }
+}
+
+// Input:
+//     async fn f<S, T>(x : S, y : &T) -> Ret;
+//
+// Output:
+//     fn f<S, T>(x : S, y : &T) -> Pin<Box<dyn Future<Output = Ret> + Send>
+// Returns the `dyn Future` type of the futures returned by the function.
+fn transform_sig(
+    sig: &mut Signature,
+    args: &RecursionArgs,
+    outer_lifetimes: &HashSet<String>,
+) -> TokenStream {
+    // Determine the original return type
+    let ret = match &sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ret) => quote!(#ret),
+    };
+
+    // Remove the asyncness of this function
+    sig.asyncness = None;
+
+    // The lifetimes we add must not collide with any lifetime already in scope.  An undeclared
+    // 'async_recursion used in the arguments is meant to refer to ours, so it only needs renaming
+    // when the user declares a lifetime of that name themselves.
+    let mut declared = outer_lifetimes.clone();
+    declared.extend(lifetime_names(&sig.generics));
+    let asr = unique_lifetime("async_recursion", &declared);
+
+    let mut taken = LifetimeNames(declared);
+    taken.visit_signature(sig);
+
+    // Find and update any references in the input arguments
+    let mut v = ReferenceVisitor::new(taken.0, asr.clone());
+    for input in &mut sig.inputs {
+        v.visit_fn_arg_mut(input);
+    }
+
+    // Does this expansion require `async_recursion to be added to the output?
+    let mut requires_lifetime = false;
+    let mut where_clause_lifetimes = vec![];
+    let mut where_clause_generics = vec![];
+
+    // Add an S : 'async_recursion bound to any generic parameter
+    for param in sig.generics.type_params() {
+        let ident = param.ident.clone();
+        where_clause_generics.push(ident);
+        requires_lifetime = true;
+    }
+
+    // Any impl Trait arguments have already been given a + 'async_recursion bound
+    if v.impl_trait {
+        requires_lifetime = true;
+    }
+
+    // Every lifetime declared by the function may be captured by the future, even if it only
+    // appears in a path (e.g. Node<'a, T>) or in the where clause
+    for param in sig.generics.lifetimes() {
+        let lt = &param.lifetime;
+        let seen = v.lifetimes.iter().any(|alt| match alt {
+            ArgLifetime::New(_) => false,
+            ArgLifetime::Existing(elt) => elt.ident == lt.ident,
+        });
+
+        if !seen && *lt != asr {
+            v.lifetimes.push(ArgLifetime::Existing(lt.clone()));
+        }
+    }
+
+    // Add an 'a : 'async_recursion bound to any lifetimes 'a appearing in the function
+    if !v.lifetimes.is_empty() {
+        requires_lifetime = true;
+        for alt in v.lifetimes {
+            if let ArgLifetime::New(lt) = &alt {
+                // If this is a new argument,
+                sig.generics.params.push(parse_quote!(#lt));
+            }
+
+            // Add a bound to the where clause
+            let lt = alt.lifetime();
+            where_clause_lifetimes.push(lt);
+        }
+    }
+
+    // If our function accepts &self, then we modify this to an explicit lifetime &'life_self,
+    // and add the bound &'life_self : 'async_recursion
+    if v.self_receiver {
+        if v.self_receiver_new_lifetime {
+            let life_self = &v.life_self;
+            sig.generics.params.push(parse_quote!(#life_self));
+        }
+        where_clause_lifetimes.extend(v.self_lifetime);
+        requires_lifetime = true;
+    }
+
+    // If our function takes self in any form, then the returned future captures Self (which may
+    // contain lifetimes and generic parameters of the impl block), so add the bound
+    // Self : 'async_recursion
+    let self_bound = sig.receiver().is_some();
+    if self_bound {
+        requires_lifetime = true;
+    }
+
+    let box_lifetime: TokenStream = if requires_lifetime {
+        // Add 'async_recursion to our generic parameters
+        sig.generics.params.push(parse_quote!(#asr));
+
+        quote!(+ #asr)
+    } else {
+        quote!()
+    };
+
+    let send_bound: TokenStream = if args.send_bound {
+        quote!(+ ::core::marker::Send)
+    } else {
+        quote!()
+    };
+
+    let sync_bound: TokenStream = if args.sync_bound {
+        quote!(+ ::core::marker::Sync)
+    } else {
+        quote!()
+    };
+
+    let where_clause = sig
+        .generics
+        .where_clause
+        .get_or_insert_with(|| WhereClause {
+            where_token: Default::default(),
+            predicates: Punctuated::new(),
+        });
+
+    // Add our S : 'async_recursion bounds
+    for generic_ident in where_clause_generics {
+        where_clause
+            .predicates
+            .push(parse_quote!(#generic_ident : #asr));
+    }
+
+    // Add our 'a : 'async_recursion bounds
+    for lifetime in where_clause_lifetimes {
+        where_clause.predicates.push(parse_quote!(#lifetime : #asr));
+    }
+
+    // Add our Self : 'async_recursion bound
+    if self_bound {
+        where_clause.predicates.push(parse_quote!(Self : #asr));
+    }
+
+    // Modify the return type
+    let future = quote! {
+        dyn ::core::future::Future<Output = #ret> #box_lifetime #send_bound #sync_bound
+    };
+    let krate = &args.krate;
+    sig.output = if args.pool {
+        parse_quote!(-> #krate::Pooled<#future>)
+    } else if let Some(size) = args.inline {
+        parse_quote!(-> #krate::Inline<#future, #size>)
+    } else if let Some(boxer) = &args.boxer {
+        parse_quote!(-> <#boxer as #krate::Boxer>::Pinned<#future>)
+    } else if args.static_pool.is_some() {
+        parse_quote!(-> #krate::StaticBox<#future>)
+    } else if args.memoize.is_some() {
+        parse_quote!(-> #krate::Memoized<#future>)
+    } else {
+        let boxed = box_path(&args.krate);
+        parse_quote!(-> ::core::pin::Pin<#boxed<#future>>)
+    };
+
+    future
+}
@@ -0,0 +1,180 @@
+/// A directed graph of calls between functions, where nodes are identified by their index.
+pub struct CallGraph {
+    edges: Vec<Vec<usize>>,
+}
+
+impl CallGraph {
+    pub fn new(nodes: usize) -> Self {
+        Self {
+            edges: vec![vec![]; nodes],
+        }
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        if !self.edges[from].contains(&to) {
+            self.edges[from].push(to);
+        }
+    }
+
+    /// Returns whether each node is part of a cycle, i.e. whether it can reach itself.
+    pub fn in_cycle(&self) -> Vec<bool> {
+        let mut in_cycle = vec![false; self.edges.len()];
+        for node in self.cyclic_components(&in_cycle).into_iter().flatten() {
+            in_cycle[node] = true;
+        }
+        in_cycle
+    }
+
+    /// Returns whether each node can reach one of the nodes marked in `targets`, counting the
+    /// targets themselves.
+    pub fn reaching(&self, targets: &[bool]) -> Vec<bool> {
+        let mut reaching = targets.to_vec();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (node, edges) in self.edges.iter().enumerate() {
+                if !reaching[node] && edges.iter().any(|&to| reaching[to]) {
+                    reaching[node] = true;
+                    changed = true;
+                }
+            }
+        }
+        reaching
+    }
+
+    /// Finds a minimal set of nodes whose removal leaves the graph without any cycles, treating
+    /// the nodes marked in `removed` as already gone.
+    ///
+    /// Finding the smallest such set is NP-hard, so we greedily remove the most connected node
+    /// of each remaining cycle and then drop any node that turns out to be unnecessary.
+    pub fn break_cycles(&self, removed: &[bool]) -> Vec<bool> {
+        let mut removed = removed.to_vec();
+        let mut chosen = vec![];
+
+        loop {
+            let cyclic = self.cyclic_components(&removed);
+            if cyclic.is_empty() {
+                break;
+            }
+
+            for component in cyclic {
+                let degree = |node: &usize| {
+                    let outgoing = self.edges[*node]
+                        .iter()
+                        .filter(|to| component.contains(to))
+                        .count();
+                    let incoming = component
+                        .iter()
+                        .filter(|from| self.edges[**from].contains(node))
+                        .count();
+                    outgoing + incoming
+                };
+
+                // Prefer the earliest node when there's a tie
+                let node = component
+                    .iter()
+                    .copied()
+                    .rev()
+                    .max_by_key(degree)
+                    .expect("components are non-empty");
+
+                removed[node] = true;
+                chosen.push(node);
+            }
+        }
+
+        for node in chosen.into_iter().rev() {
+            removed[node] = false;
+            if !self.cyclic_components(&removed).is_empty() {
+                removed[node] = true;
+            }
+        }
+
+        removed
+    }
+
+    /// Returns the strongly connected components of the graph that contain a cycle, with each
+    /// component's nodes in ascending order.
+    fn cyclic_components(&self, removed: &[bool]) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            removed,
+            index: vec![None; self.edges.len()],
+            low_link: vec![0; self.edges.len()],
+            on_stack: vec![false; self.edges.len()],
+            stack: vec![],
+            next_index: 0,
+            components: vec![],
+        };
+
+        for (node, removed) in removed.iter().enumerate() {
+            if !removed && tarjan.index[node].is_none() {
+                tarjan.connect(node);
+            }
+        }
+
+        tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.edges[component[0]].contains(&component[0])
+            })
+            .map(|mut component| {
+                component.sort_unstable();
+                component
+            })
+            .collect()
+    }
+}
+
+// Tarjan's strongly connected components algorithm
+struct Tarjan<'a> {
+    graph: &'a CallGraph,
+    removed: &'a [bool],
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn connect(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.low_link[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &to in &self.graph.edges[node] {
+            if self.removed[to] {
+                continue;
+            }
+
+            match self.index[to] {
+                None => {
+                    self.connect(to);
+                    self.low_link[node] = self.low_link[node].min(self.low_link[to]);
+                }
+                Some(index) if self.on_stack[to] => {
+                    self.low_link[node] = self.low_link[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.low_link[node]) == self.index[node] {
+            let mut component = vec![];
+            loop {
+                let member = self.stack.pop().expect("node is on the stack");
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Procedural macro implementation for the
+//! [async-recursion](https://crates.io/crates/async-recursion) crate.
+//!
+//! This crate is an implementation detail, depend on `async-recursion` instead.
+
+extern crate proc_macro;
+
+mod calls;
+mod expand;
+mod graph;
+mod memoize;
+mod parse;
+mod static_pool;
+mod tail;
+mod unroll;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse_macro_input;
+
+#[proc_macro_attribute]
+pub fn async_recursion(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut item = parse_macro_input!(input as parse::AsyncItem);
+    let args = parse_macro_input!(args as parse::RecursionArgs);
+
+    if let Err(err) = expand::expand(&mut item, &args) {
+        return TokenStream::from(err.to_compile_error());
+    }
+
+    TokenStream::from(quote!(#item))
+}
@@ -0,0 +1,102 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    visit::Visit,
+    FnArg, Ident, Pat, Path, Signature, Type,
+};
+
+use crate::{expand::FindBorrows, parse::Memoize, static_pool::FindImplTrait};
+
+/// The arguments of a function annotated with `memoize`, which are cloned into the key its calls
+/// are memoized by.
+pub struct MemoKey {
+    args: Vec<Ident>,
+    tys: Vec<Type>,
+}
+
+/// Collects the arguments of the function, which must be `'static` (as must be its future) for
+/// the memo to outlive the call that made it.
+///
+/// The memo for a function is found by its type, so the function can't be generic, or a method
+/// (whose impl block might be).
+pub fn memo_key(sig: &Signature, associated: bool) -> Result<MemoKey> {
+    if associated {
+        return Err(Error::new_spanned(
+            &sig.ident,
+            "`memoize` can't be used on methods or associated functions",
+        ));
+    }
+
+    let mut impl_trait = FindImplTrait(false);
+    for input in &sig.inputs {
+        impl_trait.visit_fn_arg(input);
+    }
+    if !sig.generics.params.is_empty() || impl_trait.0 {
+        return Err(Error::new_spanned(
+            &sig.ident,
+            "`memoize` can't be used on generic functions",
+        ));
+    }
+
+    let mut args = vec![];
+    let mut tys = vec![];
+    for input in &sig.inputs {
+        let input = match input {
+            FnArg::Typed(input) => input,
+            FnArg::Receiver(_) => unreachable!(),
+        };
+
+        let mut borrows = FindBorrows(false);
+        borrows.visit_type(&input.ty);
+        if borrows.0 {
+            return Err(Error::new_spanned(
+                &input.ty,
+                "`memoize` requires every argument to be `'static`",
+            ));
+        }
+
+        match &*input.pat {
+            Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                args.push(pat.ident.clone());
+                tys.push((*input.ty).clone());
+            }
+            pat => {
+                return Err(Error::new_spanned(
+                    pat,
+                    "`memoize` requires every argument to be a plain identifier",
+                ))
+            }
+        }
+    }
+
+    Ok(MemoKey { args, tys })
+}
+
+impl MemoKey {
+    /// Shares the call made with the same arguments, or creates `future` to make it.  `future_ty`
+    /// is the type of the `dyn Future` the function returns.
+    pub fn expand(
+        self,
+        future: TokenStream,
+        future_ty: TokenStream,
+        memoize: Memoize,
+        krate: &Path,
+    ) -> TokenStream {
+        let Self { args, tys } = self;
+        let memo = Ident::new("__ASYNC_RECURSION_MEMO", Span::mixed_site());
+        let private = quote!(#krate::__private::memoize);
+        let key = quote!((#(::core::clone::Clone::clone(&#args),)*));
+
+        match memoize {
+            Memoize::PerCall => quote! {
+                static #memo: u8 = 0;
+                #private::call(&#memo, #key, move || #future)
+            },
+            Memoize::Global => quote! {
+                static #memo: #private::Memo<(#(#tys,)*), #future_ty> = #private::Memo::new();
+                #memo.get(#key, move || #future)
+            },
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use proc_macro2::Span;
+use syn::{
+    parenthesized,
+    parse::{Error, Parse, ParseStream, Result},
+    parse_quote,
+    token::{Paren, Question},
+    Attribute, Expr, ImplItem, Item, ItemFn, ItemImpl, ItemMod, ItemTrait, LitInt, Meta, Path,
+    Token, TraitItem, TraitItemFn, Type,
+};
+
+pub enum AsyncItem {
+    Fn(ItemFn),
+    Impl(ItemImpl),
+    Trait(ItemTrait),
+    /// A method declaration inside a trait definition, which may not have a body.
+    TraitFn(TraitItemFn),
+    Mod(ItemMod),
+}
+
+impl Parse for AsyncItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        match input.parse::<Item>()? {
+            // Check that this is an async function
+            Item::Fn(item) if item.sig.asyncness.is_some() => Ok(AsyncItem::Fn(item)),
+            Item::Impl(item) => {
+                // An impl block must contain at least one async function for the attribute to
+                // make sense
+                let has_async_fn = item.items.iter().any(
+                    |impl_item| matches!(impl_item, ImplItem::Fn(f) if f.sig.asyncness.is_some()),
+                );
+
+                if !has_async_fn {
+                    return Err(Error::new(
+                        Span::call_site(),
+                        "expected an impl block containing at least one async function",
+                    ));
+                }
+
+                Ok(AsyncItem::Impl(item))
+            }
+            Item::Trait(item) => {
+                let has_async_fn = item.items.iter().any(
+                    |trait_item| matches!(trait_item, TraitItem::Fn(f) if f.sig.asyncness.is_some()),
+                );
+
+                if !has_async_fn {
+                    return Err(Error::new(
+                        Span::call_site(),
+                        "expected a trait containing at least one async function",
+                    ));
+                }
+
+                Ok(AsyncItem::Trait(item))
+            }
+            Item::Mod(item) => {
+                let has_async_fn = item.content.as_ref().is_some_and(|(_, items)| {
+                    items
+                        .iter()
+                        .any(|item| matches!(item, Item::Fn(f) if f.sig.asyncness.is_some()))
+                });
+
+                if !has_async_fn {
+                    return Err(Error::new(
+                        Span::call_site(),
+                        "expected an inline module containing at least one async function",
+                    ));
+                }
+
+                Ok(AsyncItem::Mod(item))
+            }
+            // A method declaration without a body isn't a valid item, so syn leaves it unparsed
+            Item::Verbatim(tokens) => match syn::parse2::<TraitItemFn>(tokens) {
+                Ok(item) if item.sig.asyncness.is_some() => Ok(AsyncItem::TraitFn(item)),
+                _ => Err(Error::new(Span::call_site(), "expected an async function")),
+            },
+            _ => Err(Error::new(Span::call_site(), "expected an async function")),
+        }
+    }
+}
+
+/// Returns true if the given attribute is an `#[async_recursion]` attribute, regardless of
+/// whether it was written with a path prefix (e.g. `#[async_recursion::async_recursion]`).
+pub fn is_async_recursion_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "async_recursion")
+}
+
+/// Parses the arguments of an inner `#[async_recursion(...)]` attribute.
+pub fn parse_attr_args(attr: &Attribute) -> Result<RecursionArgs> {
+    match &attr.meta {
+        Meta::Path(_) => syn::parse2(proc_macro2::TokenStream::new()),
+        _ => attr.parse_args(),
+    }
+}
+
+pub struct RecursionArgs {
+    pub send_bound: bool,
+    pub sync_bound: bool,
+    /// Keep the function as an `async fn`, and box its recursive calls instead.
+    pub call_site: bool,
+    /// Don't warn if the function never calls itself.
+    pub allow_nonrecursive: bool,
+    /// The maximum depth of recursion, past which the function panics or returns an error.
+    pub max_depth: Option<usize>,
+    /// Poll recursive calls from a driver loop, so the native stack doesn't grow with the depth of
+    /// the recursion.
+    pub trampoline: bool,
+    /// Poll the function on a new stack segment when the current one is running out.
+    pub grow_stack: bool,
+    /// Turn recursive calls in tail position into a loop.
+    pub tail: bool,
+    /// Allocate the futures from a per-thread free list of blocks, rather than with `Box`.
+    pub pool: bool,
+    /// Store the futures inline when they fit in this many bytes, rather than in a `Box`.
+    pub inline: Option<usize>,
+    /// The `Boxer` implementation used to store the futures, rather than `Box`.
+    pub boxer: Option<Type>,
+    /// Store the futures in this many slots of a `static`, rather than in a `Box`.
+    pub static_pool: Option<usize>,
+    /// Allocate the futures fallibly, returning an error from the function when that fails.
+    pub fallible: bool,
+    /// Call this many copies of the function from each other without boxing, and only box every
+    /// time the recursion goes this many calls deeper.
+    pub unroll: Option<usize>,
+    /// Fail rather than box once the recursion goes deeper than `unroll` calls.
+    pub no_alloc: bool,
+    /// Share the output of calls made with the same arguments, for as long as the memo lasts.
+    pub memoize: Option<Memoize>,
+    /// The key identifying each call, which returns an error when a call with the same key is
+    /// already on the chain of calls it is made from.
+    pub detect_cycles: Option<Expr>,
+    /// The path to the `async-recursion` crate, through which the generated code refers to its
+    /// runtime support items.
+    pub krate: Path,
+}
+
+/// How long the outputs of a function annotated with `memoize` are kept for.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Memoize {
+    /// Until the top-level call returns.
+    PerCall,
+    /// For as long as the program runs.
+    Global,
+}
+
+/// Custom keywords for parser
+mod kw {
+    syn::custom_keyword!(Send);
+    syn::custom_keyword!(Sync);
+    syn::custom_keyword!(call_site);
+    syn::custom_keyword!(allow_nonrecursive);
+    syn::custom_keyword!(max_depth);
+    syn::custom_keyword!(trampoline);
+    syn::custom_keyword!(grow_stack);
+    syn::custom_keyword!(tail);
+    syn::custom_keyword!(pool);
+    syn::custom_keyword!(inline);
+    syn::custom_keyword!(boxer);
+    syn::custom_keyword!(static_pool);
+    syn::custom_keyword!(fallible);
+    syn::custom_keyword!(unroll);
+    syn::custom_keyword!(no_alloc);
+    syn::custom_keyword!(memoize);
+    syn::custom_keyword!(global);
+    syn::custom_keyword!(detect_cycles);
+    syn::custom_keyword!(key);
+}
+
+enum Arg {
+    NotSend,
+    Sync,
+    CallSite,
+    AllowNonrecursive,
+    MaxDepth(usize),
+    Trampoline,
+    GrowStack,
+    Tail,
+    Pool,
+    Inline(usize),
+    Boxer(Type),
+    StaticPool(usize),
+    Fallible,
+    Unroll(usize),
+    NoAlloc,
+    Memoize(Memoize),
+    DetectCycles(Expr),
+    Crate(Path),
+}
+
+impl std::fmt::Display for Arg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotSend => write!(f, "?Send"),
+            Self::Sync => write!(f, "Sync"),
+            Self::CallSite => write!(f, "call_site"),
+            Self::AllowNonrecursive => write!(f, "allow_nonrecursive"),
+            Self::MaxDepth(_) => write!(f, "max_depth"),
+            Self::Trampoline => write!(f, "trampoline"),
+            Self::GrowStack => write!(f, "grow_stack"),
+            Self::Tail => write!(f, "tail"),
+            Self::Pool => write!(f, "pool"),
+            Self::Inline(_) => write!(f, "inline"),
+            Self::Boxer(_) => write!(f, "boxer"),
+            Self::StaticPool(_) => write!(f, "static_pool"),
+            Self::Fallible => write!(f, "fallible"),
+            Self::Unroll(_) => write!(f, "unroll"),
+            Self::NoAlloc => write!(f, "no_alloc"),
+            Self::Memoize(_) => write!(f, "memoize"),
+            Self::DetectCycles(_) => write!(f, "detect_cycles"),
+            Self::Crate(_) => write!(f, "crate"),
+        }
+    }
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![?]) {
+            input.parse::<Question>()?;
+            input.parse::<kw::Send>()?;
+            return Ok(Arg::NotSend);
+        }
+
+        if input.peek(Token![crate]) {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            return input.call(Path::parse_mod_style).map(Arg::Crate);
+        }
+
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::Sync) {
+            input.parse::<kw::Sync>()?;
+            Ok(Arg::Sync)
+        } else if lookahead.peek(kw::call_site) {
+            input.parse::<kw::call_site>()?;
+            Ok(Arg::CallSite)
+        } else if lookahead.peek(kw::allow_nonrecursive) {
+            input.parse::<kw::allow_nonrecursive>()?;
+            Ok(Arg::AllowNonrecursive)
+        } else if lookahead.peek(kw::max_depth) {
+            input.parse::<kw::max_depth>()?;
+            input.parse::<Token![=]>()?;
+            let depth = input.parse::<LitInt>()?;
+            match depth.base10_parse()? {
+                0 => Err(Error::new_spanned(depth, "`max_depth` must be at least 1")),
+                depth => Ok(Arg::MaxDepth(depth)),
+            }
+        } else if lookahead.peek(kw::trampoline) {
+            input.parse::<kw::trampoline>()?;
+            Ok(Arg::Trampoline)
+        } else if lookahead.peek(kw::grow_stack) {
+            input.parse::<kw::grow_stack>()?;
+            Ok(Arg::GrowStack)
+        } else if lookahead.peek(kw::tail) {
+            input.parse::<kw::tail>()?;
+            Ok(Arg::Tail)
+        } else if lookahead.peek(kw::pool) {
+            input.parse::<kw::pool>()?;
+            Ok(Arg::Pool)
+        } else if lookahead.peek(kw::inline) {
+            input.parse::<kw::inline>()?;
+            input.parse::<Token![=]>()?;
+            let size = input.parse::<LitInt>()?;
+            match size.base10_parse()? {
+                0 => Err(Error::new_spanned(size, "`inline` must be at least 1")),
+                size => Ok(Arg::Inline(size)),
+            }
+        } else if lookahead.peek(kw::boxer) {
+            input.parse::<kw::boxer>()?;
+            input.parse::<Token![=]>()?;
+            input.parse().map(Arg::Boxer)
+        } else if lookahead.peek(kw::static_pool) {
+            input.parse::<kw::static_pool>()?;
+            input.parse::<Token![=]>()?;
+            let capacity = input.parse::<LitInt>()?;
+            match capacity.base10_parse()? {
+                0 => Err(Error::new_spanned(
+                    capacity,
+                    "`static_pool` must be at least 1",
+                )),
+                capacity => Ok(Arg::StaticPool(capacity)),
+            }
+        } else if lookahead.peek(kw::fallible) {
+            input.parse::<kw::fallible>()?;
+            Ok(Arg::Fallible)
+        } else if lookahead.peek(kw::unroll) {
+            input.parse::<kw::unroll>()?;
+            input.parse::<Token![=]>()?;
+            let levels = input.parse::<LitInt>()?;
+            match levels.base10_parse()? {
+                0 => Err(Error::new_spanned(levels, "`unroll` must be at least 1")),
+                levels => Ok(Arg::Unroll(levels)),
+            }
+        } else if lookahead.peek(kw::no_alloc) {
+            input.parse::<kw::no_alloc>()?;
+            Ok(Arg::NoAlloc)
+        } else if lookahead.peek(kw::memoize) {
+            input.parse::<kw::memoize>()?;
+            if !input.peek(Paren) {
+                return Ok(Arg::Memoize(Memoize::PerCall));
+            }
+            let content;
+            parenthesized!(content in input);
+            content.parse::<kw::global>()?;
+            Ok(Arg::Memoize(Memoize::Global))
+        } else if lookahead.peek(kw::detect_cycles) {
+            input.parse::<kw::detect_cycles>()?;
+            let content;
+            parenthesized!(content in input);
+            content.parse::<kw::key>()?;
+            content.parse::<Token![=]>()?;
+            content.parse().map(Arg::DetectCycles)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl Parse for RecursionArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut send_bound: bool = true;
+        let mut sync_bound: bool = false;
+        let mut call_site: bool = false;
+        let mut allow_nonrecursive: bool = false;
+        let mut max_depth: Option<usize> = None;
+        let mut trampoline: bool = false;
+        let mut grow_stack: bool = false;
+        let mut tail: bool = false;
+        let mut pool: bool = false;
+        let mut inline: Option<usize> = None;
+        let mut boxer: Option<Type> = None;
+        let mut static_pool: Option<usize> = None;
+        let mut fallible: bool = false;
+        let mut unroll: Option<usize> = None;
+        let mut no_alloc: bool = false;
+        let mut memoize: Option<Memoize> = None;
+        let mut detect_cycles: Option<Expr> = None;
+        let mut krate: Path = parse_quote!(::async_recursion);
+
+        let args_parsed: Vec<Arg> =
+            syn::punctuated::Punctuated::<Arg, syn::Token![,]>::parse_terminated(input)
+                .map_err(|e| input.error(format!("failed to parse macro arguments: {e}")))?
+                .into_iter()
+                .collect();
+
+        // Avoid sloppy input
+        let bound_args = args_parsed
+            .iter()
+            .filter(|arg| matches!(arg, Arg::NotSend | Arg::Sync))
+            .count();
+        if bound_args > 2 {
+            return Err(Error::new(Span::call_site(), "received too many arguments"));
+        }
+
+        for (i, arg) in args_parsed.iter().enumerate() {
+            let discriminant = std::mem::discriminant(arg);
+            if args_parsed[..i]
+                .iter()
+                .any(|other| std::mem::discriminant(other) == discriminant)
+            {
+                return Err(Error::new(
+                    Span::call_site(),
+                    format!("received duplicate argument: `{arg}`"),
+                ));
+            }
+        }
+
+        for arg in args_parsed {
+            match arg {
+                Arg::NotSend => send_bound = false,
+                Arg::Sync => sync_bound = true,
+                Arg::CallSite => call_site = true,
+                Arg::AllowNonrecursive => allow_nonrecursive = true,
+                Arg::MaxDepth(depth) => max_depth = Some(depth),
+                Arg::Trampoline => trampoline = true,
+                Arg::GrowStack => grow_stack = true,
+                Arg::Tail => tail = true,
+                Arg::Pool => pool = true,
+                Arg::Inline(size) => inline = Some(size),
+                Arg::Boxer(ty) => boxer = Some(ty),
+                Arg::StaticPool(capacity) => static_pool = Some(capacity),
+                Arg::Fallible => fallible = true,
+                Arg::Unroll(levels) => unroll = Some(levels),
+                Arg::NoAlloc => no_alloc = true,
+                Arg::Memoize(lifetime) => memoize = Some(lifetime),
+                Arg::DetectCycles(key) => detect_cycles = Some(key),
+                Arg::Crate(path) => krate = path,
+            }
+        }
+
+        if no_alloc && unroll.is_none() {
+            return Err(Error::new(
+                Span::call_site(),
+                "`no_alloc` requires `unroll`",
+            ));
+        }
+
+        // The returned future isn't boxed when boxing at the call site or unrolling, so there is
+        // nothing for the bound arguments to apply to
+        let unboxed = if call_site {
+            Some("call_site")
+        } else if unroll.is_some() {
+            Some("unroll")
+        } else {
+            None
+        };
+        if let (Some(unboxed), true) = (unboxed, !send_bound || sync_bound) {
+            return Err(Error::new(
+                Span::call_site(),
+                format!("`{unboxed}` cannot be combined with `?Send` or `Sync`"),
+            ));
+        }
+
+        // Nor is the function body rewritten (other than its recursive calls)
+        let wrapping = [
+            ("max_depth", max_depth.is_some()),
+            ("trampoline", trampoline),
+            ("grow_stack", grow_stack),
+            ("tail", tail),
+            ("pool", pool),
+            ("inline", inline.is_some()),
+            ("boxer", boxer.is_some()),
+            ("static_pool", static_pool.is_some()),
+            ("fallible", fallible),
+            ("unroll", unroll.is_some()),
+            ("memoize", memoize.is_some()),
+            ("detect_cycles", detect_cycles.is_some()),
+        ];
+        if let Some(unboxed) = unboxed {
+            if let Some((name, _)) = wrapping
+                .iter()
+                .find(|(name, used)| *used && *name != unboxed)
+            {
+                return Err(Error::new(
+                    Span::call_site(),
+                    format!("`{unboxed}` cannot be combined with `{name}`"),
+                ));
+            }
+        }
+
+        // The future can only be stored in one place
+        let storage = [
+            ("pool", pool),
+            ("inline", inline.is_some()),
+            ("boxer", boxer.is_some()),
+            ("static_pool", static_pool.is_some()),
+            ("fallible", fallible),
+            ("memoize", memoize.is_some()),
+        ];
+        let mut used = storage.iter().filter(|(_, used)| *used);
+        if let (Some((first, _)), Some((second, _))) = (used.next(), used.next()) {
+            return Err(Error::new(
+                Span::call_site(),
+                format!("`{first}` cannot be combined with `{second}`"),
+            ));
+        }
+
+        // Tail calls don't nest, so they could never be found on the chain of calls
+        if detect_cycles.is_some() && tail {
+            return Err(Error::new(
+                Span::call_site(),
+                "`detect_cycles` cannot be combined with `tail`",
+            ));
+        }
+
+        Ok(Self {
+            send_bound,
+            sync_bound,
+            call_site,
+            allow_nonrecursive,
+            max_depth,
+            trampoline,
+            grow_stack,
+            tail,
+            pool,
+            inline,
+            boxer,
+            static_pool,
+            fallible,
+            unroll,
+            no_alloc,
+            memoize,
+            detect_cycles,
+            krate,
+        })
+    }
+}
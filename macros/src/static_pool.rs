@@ -0,0 +1,136 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    parse_quote,
+    visit::{self, Visit},
+    FnArg, GenericParam, Ident, Pat, Path, Signature, TypeImplTrait,
+};
+
+/// The inner `async fn` creating the futures of a function annotated with `static_pool`.
+///
+/// Its future has a type which can't be named, but is the output of a function item whose size
+/// can be found at compile time to reserve the slots for it.
+pub struct InnerFn {
+    sig: Signature,
+    args: Vec<Ident>,
+    name: String,
+    // Whether running out of slots is reported as an error rather than a panic
+    fallible: bool,
+}
+
+/// Takes the arguments of the function over to its inner function, which gets the original
+/// signature except that it takes its arguments as a single tuple.
+///
+/// The inner function can't refer to the generic parameters of the function (or of its impl
+/// block), so the function can't be generic, or a method.
+pub fn inner_fn(sig: &mut Signature, fallible: bool) -> Result<InnerFn> {
+    if let Some(receiver) = sig.receiver() {
+        return Err(Error::new_spanned(
+            receiver,
+            "`static_pool` can't be used on methods",
+        ));
+    }
+
+    let mut impl_trait = FindImplTrait(false);
+    for input in &sig.inputs {
+        impl_trait.visit_fn_arg(input);
+    }
+    let generic = sig
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, GenericParam::Lifetime(_)));
+    if generic || impl_trait.0 {
+        return Err(Error::new_spanned(
+            &sig.ident,
+            "`static_pool` can't be used on generic functions",
+        ));
+    }
+
+    let mut inner = sig.clone();
+    inner.ident = Ident::new("__async_recursion_future", Span::mixed_site());
+
+    let mut args = vec![];
+    let mut pats = vec![];
+    let mut tys = vec![];
+    for input in &mut sig.inputs {
+        let input = match input {
+            FnArg::Typed(input) => input,
+            FnArg::Receiver(_) => unreachable!(),
+        };
+
+        match &mut *input.pat {
+            Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                // The argument is only moved into the inner function, which is where it gets
+                // bound (mutably if need be)
+                args.push(pat.ident.clone());
+                pats.push(pat.clone());
+                tys.push(input.ty.clone());
+                pat.mutability = None;
+            }
+            pat => {
+                return Err(Error::new_spanned(
+                    pat,
+                    "`static_pool` requires every argument to be a plain identifier",
+                ))
+            }
+        }
+    }
+    inner.inputs = parse_quote!((#(#pats,)*): (#(#tys,)*));
+
+    Ok(InnerFn {
+        sig: inner,
+        args,
+        name: sig.ident.to_string(),
+        fallible,
+    })
+}
+
+impl InnerFn {
+    /// Declares the inner function with the given body, and moves its future into one of `N`
+    /// slots.
+    pub fn expand(self, body: TokenStream, capacity: usize, krate: &Path) -> TokenStream {
+        let Self {
+            sig,
+            args,
+            name,
+            fallible,
+        } = self;
+        let inner = &sig.ident;
+        let slots = Ident::new("__ASYNC_RECURSION_SLOTS", Span::mixed_site());
+        let private = quote!(#krate::__private::static_pool);
+
+        let bail = if fallible {
+            quote! {
+                #krate::StaticBox::ready(::core::result::Result::Err(::core::convert::From::from(
+                    #private::exhausted(#capacity),
+                )))
+            }
+        } else {
+            quote!(#private::panic_exhausted(#name, #capacity))
+        };
+
+        quote! {
+            #sig #body
+
+            static #slots: #private::StaticPool<#capacity, { #private::chunks_for_output(&#inner) }> =
+                #private::StaticPool::new();
+
+            match #slots.alloc(#inner((#(#args,)*))) {
+                ::core::result::Result::Ok(future) => future,
+                ::core::result::Result::Err(_) => #bail,
+            }
+        }
+    }
+}
+
+/// Finds any `impl Trait` argument, which makes a function generic.
+pub struct FindImplTrait(pub bool);
+
+impl Visit<'_> for FindImplTrait {
+    fn visit_type_impl_trait(&mut self, impl_trait: &TypeImplTrait) {
+        self.0 = true;
+        visit::visit_type_impl_trait(self, impl_trait);
+    }
+}
@@ -0,0 +1,256 @@
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    parse_quote,
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    Block, Expr, FnArg, Ident, Item, Lifetime, Pat, PathArguments, ReturnType, Signature, Stmt,
+    Type,
+};
+
+use crate::calls::{is_self, SelfCalls};
+
+/// Turns the calls a function makes to itself in tail position into a loop, which reassigns the
+/// arguments and jumps back to the start of the body instead of making a new call.
+///
+/// Every argument must be a plain (possibly `mut`) identifier, and every call the function makes
+/// to itself must be in tail position, i.e. returned (or awaited as the last statement) without
+/// doing anything else with its output.
+pub fn eliminate_tail_calls(
+    block: &mut Block,
+    sig: &mut Signature,
+    associated: bool,
+) -> Result<()> {
+    let mut names = vec![];
+    let mut pats = vec![];
+
+    for input in &mut sig.inputs {
+        let pat = match input {
+            FnArg::Typed(pat) => &mut pat.pat,
+            FnArg::Receiver(_) => continue,
+        };
+
+        match &mut **pat {
+            Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                // The argument is only moved into the loop, which is where it gets rebound
+                // (mutably if need be) on every iteration
+                names.push(pat.ident.clone());
+                pats.push(pat.clone());
+                pat.mutability = None;
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    pat,
+                    "`tail` requires every argument to be a plain identifier",
+                ))
+            }
+        }
+    }
+
+    let mut rewriter = TailRewriter {
+        target: SelfCalls {
+            name: &sig.ident,
+            associated,
+            receiver: sig.receiver().is_some(),
+        },
+        args: Ident::new("__async_recursion_args", Span::mixed_site()),
+        label: Lifetime::new("'__async_recursion_tail", Span::mixed_site()),
+    };
+
+    rewriter.visit_block_mut(block);
+    rewriter.rewrite_block(block);
+
+    let mut remaining = RemainingCalls {
+        target: &rewriter.target,
+        found: None,
+    };
+    remaining.visit_block(block);
+    if let Some(call) = remaining.found {
+        return Err(Error::new_spanned(
+            call,
+            "this recursive call isn't in tail position, so `tail` can't turn it into a loop",
+        ));
+    }
+
+    // Running off the end of the body breaks out of the loop, which is unreachable when the body
+    // ends with a recursive call.  Bodies without a final expression must either return `()` or
+    // diverge, in which case there is nothing to break with.
+    let TailRewriter { args, label, .. } = rewriter;
+    let mut stmts = std::mem::take(&mut block.stmts);
+    let end = match stmts.pop() {
+        Some(Stmt::Expr(value, None)) => Some(quote!(break #label #value;)),
+        last => {
+            let end = match &last {
+                Some(last) if diverges(last) => None,
+                _ => returns_unit(&sig.output).then(|| quote!(break #label;)),
+            };
+            stmts.extend(last);
+            end
+        }
+    };
+    let end = end.map(|end| quote!(#[allow(unreachable_code)] #end));
+
+    block.stmts = parse_quote! {
+        #[allow(unused_mut)]
+        let mut #args = (#(#names,)*);
+        #label: loop {
+            let (#(#pats,)*) = #args;
+            #(#stmts)*
+            #end
+        }
+    };
+
+    Ok(())
+}
+
+impl SelfCalls<'_> {
+    // Returns the arguments of a call to the function itself that can be turned into a jump,
+    // which rules out calls passing different generic arguments or a receiver other than `self`.
+    fn jump_args<'e>(&self, expr: &'e Expr) -> Option<Vec<&'e Expr>> {
+        let expr = match expr {
+            Expr::Await(expr) => &*expr.base,
+            _ => return None,
+        };
+
+        match expr {
+            Expr::Call(call) if self.path_call(call) => {
+                let func = match &*call.func {
+                    Expr::Path(func) => func,
+                    _ => return None,
+                };
+                if func
+                    .path
+                    .segments
+                    .iter()
+                    .any(|segment| !matches!(segment.arguments, PathArguments::None))
+                {
+                    return None;
+                }
+
+                let mut args = call.args.iter();
+                if self.receiver && !args.next().is_some_and(is_self) {
+                    return None;
+                }
+                Some(args.collect())
+            }
+            Expr::MethodCall(call) if self.method_call(call) && call.turbofish.is_none() => {
+                Some(call.args.iter().collect())
+            }
+            _ => None,
+        }
+    }
+}
+
+// Returns true if the statement always returns or jumps elsewhere.
+fn diverges(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Expr(Expr::Return(_) | Expr::Continue(_) | Expr::Break(_), _) => true,
+        Stmt::Expr(Expr::Block(expr), _) => expr.block.stmts.last().is_some_and(diverges),
+        _ => false,
+    }
+}
+
+fn returns_unit(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Default => true,
+        ReturnType::Type(_, ty) => matches!(&**ty, Type::Tuple(ty) if ty.elems.is_empty()),
+    }
+}
+
+// Rewrites the recursive calls in tail position.  Visiting a block rewrites the calls that are
+// returned with `return`, other tail positions are rewritten from `rewrite_block`.
+struct TailRewriter<'a> {
+    target: SelfCalls<'a>,
+    args: Ident,
+    label: Lifetime,
+}
+
+impl TailRewriter<'_> {
+    fn jump(&self, expr: &Expr) -> Option<Expr> {
+        let values = self.target.jump_args(expr)?;
+        let (args, label) = (&self.args, &self.label);
+        Some(parse_quote!({
+            #args = (#(#values,)*);
+            continue #label;
+        }))
+    }
+
+    fn rewrite_tail(&self, expr: &mut Expr) {
+        if let Some(jump) = self.jump(expr) {
+            *expr = jump;
+            return;
+        }
+
+        match expr {
+            Expr::Block(expr) => self.rewrite_block(&mut expr.block),
+            Expr::Unsafe(expr) => self.rewrite_block(&mut expr.block),
+            Expr::If(expr) => {
+                self.rewrite_block(&mut expr.then_branch);
+                if let Some((_, else_branch)) = &mut expr.else_branch {
+                    self.rewrite_tail(else_branch);
+                }
+            }
+            Expr::Match(expr) => {
+                for arm in &mut expr.arms {
+                    self.rewrite_tail(&mut arm.body);
+                }
+            }
+            Expr::Paren(expr) => self.rewrite_tail(&mut expr.expr),
+            _ => {}
+        }
+    }
+
+    fn rewrite_block(&self, block: &mut Block) {
+        match block.stmts.last_mut() {
+            Some(Stmt::Expr(expr, None)) => self.rewrite_tail(expr),
+            // Awaiting a call to a function returning `()` as the last statement
+            Some(Stmt::Expr(expr, Some(_))) => {
+                if let Some(jump) = self.jump(expr) {
+                    *expr = jump;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl VisitMut for TailRewriter<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            // These return from something other than the function
+            Expr::Closure(_) | Expr::Async(_) => return,
+            Expr::Return(ret) => {
+                if let Some(value) = &mut ret.expr {
+                    match self.jump(value) {
+                        Some(jump) => *expr = jump,
+                        None => self.rewrite_tail(value),
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        visit_mut::visit_expr_mut(self, expr);
+    }
+
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
+
+// Finds a recursive call left after rewriting the ones in tail position.
+struct RemainingCalls<'a, 'ast> {
+    target: &'a SelfCalls<'a>,
+    found: Option<&'ast Expr>,
+}
+
+impl<'ast> Visit<'ast> for RemainingCalls<'_, 'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if self.found.is_none() && self.target.is_call(expr) {
+            self.found = Some(expr);
+        }
+
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_item(&mut self, _: &'ast Item) {}
+}
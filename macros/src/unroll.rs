@@ -0,0 +1,166 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    parse_quote,
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    Block, Expr, GenericParam, Ident, Item, ItemFn, Path, ReturnType, Signature, Stmt, Token,
+};
+
+use crate::{
+    calls::SelfCalls,
+    expand::{box_path, returns_result},
+};
+
+/// Unrolls the recursion of a function into `levels` copies of it, which call each other in turn
+/// without boxing.  The last copy boxes its calls to the function, going back to the first copy,
+/// or when `no_alloc` is set, fails them as going too deep.
+///
+/// The copies are declared inside the function, so they can't refer to its generic parameters (or
+/// to those of its impl block), which rules out generic functions and methods.
+pub fn unroll(
+    sig: &Signature,
+    block: &mut Block,
+    associated: bool,
+    levels: usize,
+    no_alloc: bool,
+    krate: &Path,
+) -> Result<()> {
+    if associated {
+        return Err(Error::new_spanned(
+            &sig.ident,
+            "`unroll` can't be used on methods or associated functions",
+        ));
+    }
+    if sig
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, GenericParam::Lifetime(_)))
+    {
+        return Err(Error::new_spanned(
+            &sig.ident,
+            "`unroll` can't be used on generic functions",
+        ));
+    }
+
+    let target = SelfCalls {
+        name: &sig.ident,
+        associated,
+        receiver: false,
+    };
+    let original = &*block;
+    let level = |depth: usize| {
+        Ident::new(
+            &format!("__async_recursion_level_{depth}"),
+            Span::mixed_site(),
+        )
+    };
+
+    let mut copies = vec![];
+    for depth in 0..levels {
+        let mut body = original.clone();
+
+        let call = if depth + 1 < levels {
+            Call::Level(level(depth + 1))
+        } else if no_alloc {
+            Call::Exceeded(exceeded(sig, levels, krate))
+        } else {
+            Call::Boxed(box_path(krate))
+        };
+        let mut rewriter = CallRewriter {
+            target: &target,
+            call,
+            calls: 0,
+        };
+        rewriter.visit_block_mut(&mut body);
+
+        // A function that never calls itself has nothing to unroll
+        if depth == 0 && rewriter.calls == 0 {
+            return Ok(());
+        }
+
+        copies.push(body);
+    }
+
+    let mut copies = copies.into_iter();
+    *block = copies.next().expect("there is at least one level");
+    let items: Vec<Stmt> = copies
+        .enumerate()
+        .map(|(i, body)| {
+            let mut sig = sig.clone();
+            sig.ident = level(i + 1);
+            Stmt::Item(Item::Fn(ItemFn {
+                attrs: vec![],
+                vis: syn::Visibility::Inherited,
+                sig,
+                block: Box::new(body),
+            }))
+        })
+        .collect();
+    block.stmts.splice(0..0, items);
+
+    Ok(())
+}
+
+// Stands in for a call that would go deeper than the last copy, either returning an error or
+// panicking (once the arguments have been evaluated).
+fn exceeded(sig: &Signature, levels: usize, krate: &Path) -> TokenStream {
+    let ret = match &sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ret) => quote!(#ret),
+    };
+
+    if returns_result(&sig.output) {
+        quote! {
+            ::core::future::ready::<#ret>(::core::result::Result::Err(::core::convert::From::from(
+                #krate::__private::depth::exceeded(#levels),
+            )))
+        }
+    } else {
+        let name = sig.ident.to_string();
+        quote!(#krate::__private::depth::panic_exceeded_future::<#ret>(#name, #levels))
+    }
+}
+
+enum Call {
+    // Call the next copy of the function
+    Level(Ident),
+    // Box the call to the function itself
+    Boxed(TokenStream),
+    Exceeded(TokenStream),
+}
+
+struct CallRewriter<'a> {
+    target: &'a SelfCalls<'a>,
+    call: Call,
+    calls: usize,
+}
+
+impl VisitMut for CallRewriter<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+
+        let call = match expr {
+            Expr::Call(call) if self.target.path_call(call) => call,
+            _ => return,
+        };
+        self.calls += 1;
+
+        match &self.call {
+            Call::Level(level) => *call.func = parse_quote!(#level),
+            Call::Boxed(boxed) => *expr = parse_quote!(#boxed::pin(#call)),
+            Call::Exceeded(exceeded) => {
+                let args: &Punctuated<Expr, Token![,]> = &call.args;
+                let args = args.iter();
+                *expr = parse_quote!({
+                    let _ = (#(#args,)*);
+                    #exceeded
+                });
+            }
+        }
+    }
+
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
@@ -0,0 +1,82 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
+
+use crate::coerce::CoerceFuture;
+
+/// A strategy for storing the futures of a function annotated with
+/// `#[async_recursion(boxer = path::to::Strategy)]`, in place of `Box::pin`.
+///
+/// The function returns a `Strategy::Pinned<dyn Future<Output = T> + Send>`, which should be
+/// `Send` (or `Sync`) whenever the future it holds is, and creates it with `Strategy::pin`.
+///
+/// ```rust
+/// use async_recursion::{async_recursion, BoxPin, Boxer, CoerceFuture};
+/// use core::{future::Future, pin::Pin};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static CALLS: AtomicUsize = AtomicUsize::new(0);
+///
+/// /// Counts the futures it boxes.
+/// struct Counted;
+///
+/// impl Boxer for Counted {
+///     type Pinned<F: ?Sized + Future> = Pin<Box<F>>;
+///
+///     fn pin<G, F: ?Sized + Future + CoerceFuture<G>>(future: G) -> Self::Pinned<F> {
+///         CALLS.fetch_add(1, Ordering::Relaxed);
+///         BoxPin::pin(future)
+///     }
+/// }
+///
+/// #[async_recursion(boxer = Counted)]
+/// async fn countdown(n: u32) {
+///     if n > 0 {
+///         countdown(n - 1).await
+///     }
+/// }
+/// ```
+pub trait Boxer {
+    /// The pinned pointer to a future of type `F`.
+    type Pinned<F: ?Sized + Future>: Future<Output = F::Output>;
+
+    /// Moves a future of type `G` behind a pinned pointer, coercing it to the (usually `dyn
+    /// Future`) type `F` with `CoerceFuture`.
+    fn pin<G, F: ?Sized + Future + CoerceFuture<G>>(future: G) -> Self::Pinned<F>;
+}
+
+/// The strategy used by default, which stores the futures with `Box::pin`.
+#[cfg(feature = "alloc")]
+pub struct BoxPin;
+
+#[cfg(feature = "alloc")]
+impl Boxer for BoxPin {
+    type Pinned<F: ?Sized + Future> = Pin<Box<F>>;
+
+    fn pin<G, F: ?Sized + Future + CoerceFuture<G>>(future: G) -> Self::Pinned<F> {
+        let future = Box::into_raw(Box::new(future));
+        // Safety: `CoerceFuture` guarantees that `coerce` returns a pointer to the future owned by
+        // the box
+        unsafe { Pin::new_unchecked(Box::from_raw(F::coerce(future))) }
+    }
+}
+
+/// Stores the futures with `Box::pin_in`, in a default constructed allocator of type `A`.
+///
+/// This requires the `allocator_api` feature, and a nightly compiler.
+#[cfg(feature = "allocator_api")]
+pub struct BoxPinIn<A>(core::marker::PhantomData<A>);
+
+#[cfg(feature = "allocator_api")]
+impl<A: core::alloc::Allocator + Default> Boxer for BoxPinIn<A> {
+    type Pinned<F: ?Sized + Future> = Pin<Box<F, A>>;
+
+    fn pin<G, F: ?Sized + Future + CoerceFuture<G>>(future: G) -> Self::Pinned<F> {
+        let (future, alloc) = Box::into_raw_with_allocator(Box::new_in(future, A::default()));
+        // Safety: `CoerceFuture` guarantees that `coerce` returns a pointer to the future owned by
+        // the box
+        unsafe { Pin::new_unchecked(Box::from_raw_in(F::coerce(future), alloc)) }
+    }
+}
@@ -0,0 +1,40 @@
+use core::future::Future;
+
+/// Implemented by the `dyn Future` types that a future of type `G` can be coerced to, so that
+/// smart pointers other than `Box` can hold the futures returned by annotated functions.
+///
+/// Custom smart pointers can't be coerced to trait objects on stable Rust, so instead they coerce
+/// a raw pointer to the future when they are created (see [`Boxer`](crate::Boxer)).
+///
+/// This crate implements it for `dyn Future` with any combination of `Send` and `Sync`, which are
+/// the only types the macro needs.
+///
+/// # Safety
+///
+/// `coerce` must behave like an unsizing coercion: the pointer it returns must point to the same
+/// `G` it was given, so that reading, dropping and deallocating the `G` through it (which relies
+/// on its size and alignment as given by `Self`'s metadata) works exactly as through the original
+/// pointer.
+pub unsafe trait CoerceFuture<G> {
+    /// Coerces a pointer to a future of type `G` into a pointer to `Self`, with the same address.
+    fn coerce(future: *mut G) -> *mut Self;
+}
+
+macro_rules! coerce_future {
+    ($($bound:ident)*) => {
+        // Safety: this is an unsizing coercion
+        unsafe impl<'a, G> CoerceFuture<G> for dyn Future<Output = G::Output> $(+ $bound)* + 'a
+        where
+            G: Future $(+ $bound)* + 'a,
+        {
+            fn coerce(future: *mut G) -> *mut Self {
+                future
+            }
+        }
+    };
+}
+
+coerce_future!();
+coerce_future!(Send);
+coerce_future!(Sync);
+coerce_future!(Send Sync);
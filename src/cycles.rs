@@ -0,0 +1,95 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::fmt;
+
+use crate::scope::{self, Scope};
+
+/// The error returned by a function annotated with `#[async_recursion(detect_cycles(key = ..))]`
+/// when a call is made with the same key as one of the calls it is made from.
+///
+/// Functions return this error (converted with `E::from`) instead of recursing forever, which
+/// requires them to return `Result<T, E>` where `E: From<CycleDetected<K>>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleDetected<K> {
+    path: Vec<K>,
+}
+
+impl<K> CycleDetected<K> {
+    /// The keys of the calls making up the cycle, from the earlier call with the repeated key
+    /// down to the call that repeated it, so that the first and last keys are equal.
+    pub fn path(&self) -> &[K] {
+        &self.path
+    }
+
+    /// Returns the keys of the calls making up the cycle, as returned by [`path`](Self::path).
+    pub fn into_path(self) -> Vec<K> {
+        self.path
+    }
+}
+
+impl<K: fmt::Debug> fmt::Display for CycleDetected<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "recursion cycle detected: ")?;
+        for (i, key) in self.path.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{key:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for CycleDetected<K> {}
+
+/// The key of a call, linked to the keys of the calls it is (directly or not) made from.
+///
+/// The whole chain is captured when the future is created, rather than found among the calls being
+/// polled, as a trampolined call is polled from a loop instead of from inside the calls it is made
+/// from.
+pub struct Chain<K> {
+    key: K,
+    parent: Option<Arc<Chain<K>>>,
+}
+
+impl<K> Drop for Chain<K> {
+    // Unlinks the chain one call at a time, so that dropping a long one doesn't overflow the stack
+    fn drop(&mut self) {
+        let mut parent = self.parent.take();
+        while let Some(chain) = parent {
+            parent = Arc::into_inner(chain).and_then(|mut chain| chain.parent.take());
+        }
+    }
+}
+
+/// Looks for a call to the function identified by `fn_key` with the same `key` among the calls a
+/// new call is made from, returning the chain of keys leading to the new call along with any cycle
+/// it closes.
+///
+/// As this is worked out when the future is created (i.e. while its caller is being polled), it
+/// doesn't matter which thread polls it afterwards, or from where.
+pub fn find_cycle<K: PartialEq + Clone + 'static>(
+    fn_key: &'static u8,
+    key: K,
+) -> (Arc<Chain<K>>, Option<CycleDetected<K>>) {
+    // Calls to other functions (or with other types of keys) aren't part of the chain
+    let parent = scope::find(fn_key, |caller: &Arc<Chain<K>>| Some(caller.clone()));
+
+    let mut path = Vec::new();
+    let mut caller = parent.as_deref();
+    let mut cycle = None;
+    while let Some(chain) = caller {
+        path.push(chain.key.clone());
+        if chain.key == key {
+            path.reverse();
+            path.push(key.clone());
+            cycle = Some(CycleDetected { path });
+            break;
+        }
+        caller = chain.parent.as_deref();
+    }
+
+    (Arc::new(Chain { key, parent }), cycle)
+}
+
+/// Makes the chain of keys leading to a call known to the calls it makes while being polled.
+pub type WithCycleKey<K, F> = Scope<Arc<Chain<K>>, F>;
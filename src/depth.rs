@@ -0,0 +1,67 @@
+use core::{fmt, future::Ready};
+
+#[cfg(feature = "std")]
+use crate::scope::{self, Scope};
+
+/// The error returned by a function annotated with `#[async_recursion(max_depth = N)]` when its
+/// recursion goes more than `N` calls deep, or by one annotated with
+/// `#[async_recursion(unroll = N, no_alloc)]` when it would go more than `N` calls deep.
+///
+/// Functions returning `Result<T, E>` return this error (converted with `E::from`) instead of
+/// panicking, as long as `E: From<DepthExceeded>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthExceeded {
+    max_depth: usize,
+}
+
+impl DepthExceeded {
+    /// The maximum depth that was exceeded.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+impl fmt::Display for DepthExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "recursion exceeded the maximum depth of {}",
+            self.max_depth
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DepthExceeded {}
+
+/// Returns the depth of a new call to the function identified by `key`, which is one more than
+/// the depth of the innermost call to the same function currently being polled on this thread.
+///
+/// As the depth is worked out when the future is created (i.e. while its caller is being
+/// polled) and then stored in the future, it doesn't matter which thread polls it afterwards.
+#[cfg(feature = "std")]
+pub fn child_depth(key: &'static u8) -> usize {
+    scope::find(key, |depth: &usize| Some(depth + 1)).unwrap_or(1)
+}
+
+pub fn exceeded(max_depth: usize) -> DepthExceeded {
+    DepthExceeded { max_depth }
+}
+
+#[track_caller]
+pub fn panic_exceeded(name: &str, max_depth: usize) -> ! {
+    panic!(
+        "`{}` exceeded the maximum recursion depth of {}",
+        name, max_depth
+    )
+}
+
+/// Stands in for a call that would go too deep, so that the call panics when it is made.
+#[track_caller]
+pub fn panic_exceeded_future<T>(name: &str, max_depth: usize) -> Ready<T> {
+    panic_exceeded(name, max_depth)
+}
+
+/// Makes the depth of a call known to the calls it makes while being polled.
+#[cfg(feature = "std")]
+pub type WithDepth<F> = Scope<usize, F>;
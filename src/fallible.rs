@@ -0,0 +1,82 @@
+use alloc::{alloc::alloc, boxed::Box};
+use core::{
+    alloc::Layout,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    ptr::NonNull,
+    task::{Context, Poll},
+};
+
+use crate::coerce::CoerceFuture;
+
+/// The error returned by a function annotated with `#[async_recursion(fallible)]` when there
+/// isn't enough memory to allocate its future.
+///
+/// The function returns this error (converted with `E::from`) from its `Result<T, E>`, which
+/// requires `E: From<AllocError>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError {
+    _private: (),
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
+
+/// Implemented by the `Result<T, E>` types that can report an `AllocError`.
+pub trait FromAllocError {
+    fn from_alloc_error(err: AllocError) -> Self;
+}
+
+impl<T, E: From<AllocError>> FromAllocError for Result<T, E> {
+    fn from_alloc_error(err: AllocError) -> Self {
+        Err(E::from(err))
+    }
+}
+
+/// A future which returns an `AllocError` straight away.  It has no size, so boxing it never
+/// allocates.
+pub struct Failed<R>(PhantomData<fn() -> R>);
+
+impl<R: FromAllocError> Future for Failed<R> {
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<R> {
+        Poll::Ready(R::from_alloc_error(AllocError { _private: () }))
+    }
+}
+
+/// Like `Box::pin`, except that when the allocation fails the future is dropped and replaced by
+/// one returning an `AllocError`.
+pub fn try_pin<G, F>(future: G) -> Pin<Box<F>>
+where
+    F: ?Sized + Future + CoerceFuture<G> + CoerceFuture<Failed<F::Output>>,
+    F::Output: FromAllocError,
+{
+    let layout = Layout::new::<G>();
+    let ptr = if layout.size() == 0 {
+        NonNull::<G>::dangling().as_ptr()
+    } else {
+        // Safety: the layout has a non-zero size
+        unsafe { alloc(layout) }.cast::<G>()
+    };
+
+    // Safety: the pointer was allocated by the global allocator with the layout of the future it
+    // is given, just like `Box` would have, and `coerce` returns the same pointer
+    unsafe {
+        if ptr.is_null() {
+            let failed: *mut Failed<F::Output> = NonNull::dangling().as_ptr();
+            return Pin::new_unchecked(Box::from_raw(F::coerce(failed)));
+        }
+
+        ptr.write(future);
+        Pin::new_unchecked(Box::from_raw(<F as CoerceFuture<G>>::coerce(ptr)))
+    }
+}
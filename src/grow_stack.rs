@@ -0,0 +1,31 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+// How little native stack may be left before polling moves to a new segment, and how big the new
+// segments are.
+const RED_ZONE: usize = 64 * 1024;
+const SEGMENT_SIZE: usize = 1024 * 1024;
+
+/// Polls a future on a freshly allocated stack segment whenever the current one is running out.
+pub struct GrowStack<F> {
+    future: F,
+}
+
+impl<F> GrowStack<F> {
+    pub fn new(future: F) -> Self {
+        Self { future }
+    }
+}
+
+impl<F: Future> Future for GrowStack<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: `future` is never moved out of `self`
+        let future = unsafe { self.map_unchecked_mut(|this| &mut this.future) };
+        stacker::maybe_grow(RED_ZONE, SEGMENT_SIZE, || future.poll(cx))
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::boxed::Box;
+use core::{
+    fmt,
+    future::Future,
+    marker::PhantomPinned,
+    mem::{self, MaybeUninit},
+    pin::Pin,
+    ptr,
+    task::{Context, Poll},
+};
+
+use crate::{
+    boxer::{BoxPin, Boxer},
+    coerce::CoerceFuture,
+};
+
+// The bytes a future is stored in, aligned enough for most futures.
+#[repr(C, align(16))]
+struct Buffer<const N: usize>(MaybeUninit<[u8; N]>);
+
+enum Storage<F: ?Sized, const N: usize> {
+    Inline {
+        buffer: Buffer<N>,
+        // Turns a pointer to the buffer into a pointer to the future stored in it
+        future: unsafe fn(*mut u8) -> *mut F,
+        // Moves the future out of the buffer and onto the heap
+        boxed: unsafe fn(*mut u8) -> Pin<Box<F>>,
+    },
+    Boxed(Pin<Box<F>>),
+    // The future has been moved out by `into_boxed`
+    Moved,
+}
+
+/// The future returned by a function annotated with `#[async_recursion(inline = N)]`.
+///
+/// The future is stored inline in an `N` byte buffer when it fits, and in a `Box` otherwise.  As
+/// the buffer can't move once the future has been polled, `Inline` isn't `Unpin`, so it has to be
+/// pinned (e.g. with `core::pin::pin!`) to be polled by hand.  It converts into a
+/// `Pin<Box<dyn Future>>` wherever one is needed.
+pub struct Inline<F: ?Sized, const N: usize> {
+    storage: Storage<F, N>,
+    _pinned: PhantomPinned,
+}
+
+unsafe fn future_at<G, F: ?Sized + CoerceFuture<G>>(buffer: *mut u8) -> *mut F {
+    F::coerce(buffer.cast::<G>())
+}
+
+unsafe fn box_at<G, F: ?Sized + Future + CoerceFuture<G>>(buffer: *mut u8) -> Pin<Box<F>> {
+    BoxPin::pin(buffer.cast::<G>().read())
+}
+
+impl<F: ?Sized, const N: usize> Inline<F, N> {
+    #[doc(hidden)]
+    pub fn new<G>(future: G) -> Self
+    where
+        F: Future + CoerceFuture<G>,
+    {
+        let fits = mem::size_of::<G>() <= N && mem::align_of::<G>() <= mem::align_of::<Buffer<N>>();
+        let storage = if fits {
+            let mut buffer = Buffer(MaybeUninit::uninit());
+            // Safety: the buffer is large enough and aligned enough to hold a `G`
+            unsafe { buffer.0.as_mut_ptr().cast::<G>().write(future) };
+            Storage::Inline {
+                buffer,
+                future: future_at::<G, F>,
+                boxed: box_at::<G, F>,
+            }
+        } else {
+            Storage::Boxed(BoxPin::pin(future))
+        };
+
+        Self {
+            storage,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Returns true if the future is stored inline, rather than on the heap.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline { .. })
+    }
+
+    /// Moves the future onto the heap, if it isn't there already.
+    pub fn into_boxed(mut self) -> Pin<Box<F>> {
+        match mem::replace(&mut self.storage, Storage::Moved) {
+            // Safety: the future has never been polled, as `self` would have been pinned in place
+            // for that, and the buffer is forgotten once the future is moved out of it
+            Storage::Inline {
+                mut buffer, boxed, ..
+            } => unsafe { boxed(buffer.0.as_mut_ptr().cast()) },
+            Storage::Boxed(future) => future,
+            Storage::Moved => unreachable!(),
+        }
+    }
+}
+
+// Safety: `Inline<F, N>` owns an `F` just like `Box<F>` does
+unsafe impl<F: ?Sized + Send, const N: usize> Send for Inline<F, N> {}
+unsafe impl<F: ?Sized + Sync, const N: usize> Sync for Inline<F, N> {}
+
+impl<F: ?Sized + Future, const N: usize> Future for Inline<F, N> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the buffer never moves once `self` is pinned
+        match &mut unsafe { self.get_unchecked_mut() }.storage {
+            Storage::Inline { buffer, future, .. } => unsafe {
+                Pin::new_unchecked(&mut *future(buffer.0.as_mut_ptr().cast())).poll(cx)
+            },
+            Storage::Boxed(future) => future.as_mut().poll(cx),
+            Storage::Moved => unreachable!(),
+        }
+    }
+}
+
+impl<F: ?Sized, const N: usize> Drop for Inline<F, N> {
+    fn drop(&mut self) {
+        if let Storage::Inline { buffer, future, .. } = &mut self.storage {
+            // Safety: the buffer holds the future until it is moved out by `into_boxed`
+            unsafe { ptr::drop_in_place(future(buffer.0.as_mut_ptr().cast())) };
+        }
+    }
+}
+
+macro_rules! into_boxed {
+    ($($bound:ident)*) => {
+        impl<'a, T, const N: usize> From<Inline<dyn Future<Output = T> $(+ $bound)* + 'a, N>>
+            for Pin<Box<dyn Future<Output = T> $(+ $bound)* + 'a>>
+        {
+            fn from(future: Inline<dyn Future<Output = T> $(+ $bound)* + 'a, N>) -> Self {
+                future.into_boxed()
+            }
+        }
+    };
+}
+
+into_boxed!();
+into_boxed!(Send);
+into_boxed!(Sync);
+into_boxed!(Send Sync);
+
+impl<F: ?Sized, const N: usize> fmt::Debug for Inline<F, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inline")
+            .field("inline", &self.is_inline())
+            .finish_non_exhaustive()
+    }
+}
@@ -87,6 +87,455 @@
 //! - `#[async_recursion(?Send)]` modifies your function to return a boxed [`Future`] _without_ a [`Send`] bound.
 //! - `#[async_recursion(Sync)]` modifies your function to return a boxed [`Future`] with [`Send`] and [`Sync`] bounds.
 //!
+//! ## Impl blocks
+//!
+//! The attribute can also be placed on an `impl` block, in which case every `async fn` inside
+//! the block is rewritten. Other items are left untouched, and individual methods can override
+//! the arguments given to the block with their own attribute:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! struct Walker;
+//!
+//! #[async_recursion]
+//! impl Walker {
+//!     async fn is_even(&self, n: u32) -> bool {
+//!         n == 0 || self.is_odd(n - 1).await
+//!     }
+//!
+//!     async fn is_odd(&self, n: u32) -> bool {
+//!         n != 0 && self.is_even(n - 1).await
+//!     }
+//!
+//!     #[async_recursion(?Send)]
+//!     async fn returned_future_is_not_send(&self) {
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! ## Traits
+//!
+//! Trait definitions are supported in the same way, either by annotating the whole trait or
+//! individual methods. Method declarations are rewritten to the same signature as their
+//! implementations, so the trait stays object safe and implementations annotated with
+//! `#[async_recursion]` match it exactly:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion]
+//! trait Countdown: Sync {
+//!     async fn countdown(&self, n: u32) -> u32;
+//!
+//!     async fn countdown_twice(&self, n: u32) -> u32 {
+//!         self.countdown(n).await + self.countdown(n).await
+//!     }
+//! }
+//!
+//! struct Counter;
+//!
+//! #[async_recursion]
+//! impl Countdown for Counter {
+//!     async fn countdown(&self, n: u32) -> u32 {
+//!         if n == 0 { 0 } else { 1 + self.countdown(n - 1).await }
+//!     }
+//! }
+//!
+//! let counter: Box<dyn Countdown> = Box::new(Counter);
+//! ```
+//!
+//! Default method bodies hold on to `self` inside the returned [`Future`], so with the default
+//! [`Send`] bound they additionally require `Self: Sync` (for `&self`), the receiver type to be
+//! [`Send`] (for typed receivers such as `self: Arc<Self>`) or `Self: Send` (for all other
+//! receivers).
+//!
+//! ## Modules
+//!
+//! Placing the attribute on an inline module finds every cycle of calls between the async
+//! functions inside it, and only rewrites as few functions as are needed to break each cycle.
+//! Functions that aren't part of a cycle are left as they are:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion]
+//! mod parser {
+//!     pub async fn parse_expr(input: &[u8]) -> usize {
+//!         parse_term(input).await
+//!     }
+//!
+//!     async fn parse_term(input: &[u8]) -> usize {
+//!         match input.split_first() {
+//!             Some((b'(', rest)) => parse_expr(rest).await,
+//!             _ => input.len(),
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! A function inside the module can carry its own attribute, in which case it is always rewritten
+//! using the arguments given there.
+//!
+//! ## Call-site boxing
+//!
+//! Rewriting the signature means the returned future is always boxed and always `Send` (unless
+//! `?Send` is given). With the `call_site` option the signature is left alone, and the recursive
+//! calls inside the body are boxed instead:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(call_site)]
+//! async fn fib(n: u32) -> u64 {
+//!     match n {
+//!         0 | 1 => 1,
+//!         _ => fib(n - 1).await + fib(n - 2).await,
+//!     }
+//! }
+//! ```
+//!
+//! Callers outside the recursion receive an unboxed future, which is `Send` exactly when the body
+//! allows it. Only direct calls (`fib(..)`, `self::fib(..)`, `Self::fib(..)` and `self.fib(..)`)
+//! are rewritten; calls hidden inside macros are not. `call_site` can also be placed on impl blocks,
+//! traits and modules, and cannot be combined with `?Send` or `Sync`.
+//!
+//! ## Non-recursive functions
+//!
+//! Boxing the future costs an allocation on every call, which is wasted on a function that never
+//! recurses. The macro warns when an annotated function doesn't call itself, either directly or
+//! through other functions rewritten by the same attribute. Calls that can't be followed, such as
+//! awaiting a function or calling a method on `self` defined elsewhere, are assumed to recurse.
+//! Trait methods, and the methods of a trait impl the attribute is placed on, are never warned
+//! about, as their signature is set by the trait rather than by whether they recurse. Pass
+//! `allow_nonrecursive` to silence the warning:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(allow_nonrecursive)]
+//! async fn double(n: u32) -> u32 {
+//!     n * 2
+//! }
+//! ```
+//!
+//! ## `no_std` support
+//!
+//! With its default `std` feature disabled this crate is `#![no_std]`, and the generated code only
+//! refers to items through fully qualified paths, so it works in `#![no_std]` crates and isn't
+//! affected by a user type named `Box` or by `#![no_implicit_prelude]`. Likewise, the lifetimes the
+//! macro gives to elided references are prefixed with `__async_recursion_`, and `'async_recursion`
+//! is renamed whenever it would clash with a lifetime already declared by the function, or by the
+//! impl block or trait carrying the attribute.
+//!
+//! The `max_depth`, `trampoline`, `grow_stack`, `pool`, `memoize` and `detect_cycles` options are
+//! only available with the `std` feature (and `grow_stack` also needs a feature of its own).
+//! Disabling the `alloc` feature as well removes the need for an allocator, in which case futures
+//! can only be stored with the `static_pool` option or a custom `boxer`.
+//!
+//! ## Re-exporting the macro
+//!
+//! The generated code refers to items of this crate through `::async_recursion`. If you re-export
+//! the macro from your own crate, so that your users don't depend on `async-recursion` directly, use
+//! the `crate` option to tell the macro where to find it:
+//!
+//! ```rust
+//! # mod my_facade { pub mod async_recursion { pub use ::async_recursion::*; } }
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(crate = my_facade::async_recursion)]
+//! async fn fib(n: u32) -> u64 {
+//!     match n {
+//!         0 | 1 => 1,
+//!         _ => fib(n - 1).await + fib(n - 2).await,
+//!     }
+//! }
+//! ```
+//!
+//! ## Limiting the depth of recursion
+//!
+//! Deeply nested or cyclic input can make a recursive function recurse until the process runs out
+//! of memory. The `max_depth` option limits how deep the recursion starting from each outermost call
+//! may go:
+//!
+//! ```rust
+//! # use async_recursion::{async_recursion, DepthExceeded};
+//! #[derive(Debug)]
+//! enum Error {
+//!     TooDeep,
+//! }
+//!
+//! impl From<DepthExceeded> for Error {
+//!     fn from(_: DepthExceeded) -> Self {
+//!         Error::TooDeep
+//!     }
+//! }
+//!
+//! #[async_recursion(max_depth = 64)]
+//! async fn depth(input: &[u8]) -> Result<usize, Error> {
+//!     match input.split_first() {
+//!         Some((b'(', rest)) => Ok(1 + depth(rest).await?),
+//!         _ => Ok(0),
+//!     }
+//! }
+//! ```
+//!
+//! A function returning `Result<T, E>` returns an error converted from [`DepthExceeded`] once the
+//! limit is exceeded, and any other function panics. The depth is stored in the future itself, so it
+//! is tracked correctly even when the future moves between threads. This option requires the `std`
+//! feature, which is enabled by default.
+//!
+//! ## Trampolining deep recursion
+//!
+//! Although every call is boxed, each future still polls the call it is waiting on from inside its
+//! own `poll`, so polling a recursion that is 100,000 calls deep needs 100,000 native stack frames.
+//! The `trampoline` option hands recursive calls over to a driver that polls them one at a time
+//! from a loop instead, so the native stack stays the same size however deep the recursion goes:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(trampoline)]
+//! async fn sum(n: u64) -> u64 {
+//!     if n == 0 { 0 } else { n + sum(n - 1).await }
+//! }
+//! ```
+//!
+//! Each poll of the outermost call sets up a driver, which is shared by every trampolined function
+//! it ends up calling. The driver only polls a call when its caller does, but once the caller's
+//! `poll` has returned, so the arguments of a trampolined function can't borrow anything and have
+//! to be `'static`. This option requires the `std` feature.
+//!
+//! In the same way, dropping a boxed future drops the call it is waiting on from inside its own
+//! `drop`, so cancelling a deep recursion (e.g. on a timeout) can overflow the stack even when
+//! polling it didn't. Trampolined calls are torn down one at a time from a loop instead, whether it
+//! is the outermost call being dropped or a caller giving up on one of its calls.
+//!
+//! ## Growing the stack
+//!
+//! As a lighter alternative to `trampoline`, the `grow_stack` option checks how much native stack
+//! is left each time a call is polled, and when it runs low, carries on polling on a freshly
+//! allocated stack segment (using the [`stacker`](https://crates.io/crates/stacker) crate). It
+//! requires the `grow_stack` feature, so that nobody else pays for it:
+//!
+//! ```toml
+//! [dependencies]
+//! async-recursion = { version = "1", features = ["grow_stack"] }
+//! ```
+//!
+//! ## Tail calls
+//!
+//! A function whose recursive calls are all in tail position, i.e. their output is returned as it
+//! is, can have them turned into a loop with the `tail` option. Rather than making a new call, the
+//! function reassigns its arguments and jumps back to the start of its body, so it runs in constant
+//! memory however many times it recurses:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(tail)]
+//! async fn sum(n: u64, acc: u64) -> u64 {
+//!     if n == 0 {
+//!         return acc;
+//!     }
+//!
+//!     sum(n - 1, acc + n).await
+//! }
+//! ```
+//!
+//! Calls are in tail position when they are the final expression of the body (or of an `if`,
+//! `match` or block in tail position), when they are returned with `return`, or when they are
+//! awaited as the last statement of a function returning `()`. Methods may call themselves on
+//! `self` or through `Self`. Every argument must be a plain identifier, and a recursive call that
+//! isn't in tail position is a compile error.
+//!
+//! ## Pooling allocations
+//!
+//! Every call to a recursive function allocates a new box for its future, which can add up when a
+//! function recurses a lot. The `pool` option instead takes the memory for its futures from a free
+//! list kept by each thread for each function, and hands it back as soon as a future completes or is
+//! dropped:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(pool)]
+//! async fn fib(n: u32) -> u64 {
+//!     match n {
+//!         0 | 1 => n as u64,
+//!         _ => fib(n - 1).await + fib(n - 2).await,
+//!     }
+//! }
+//! ```
+//!
+//! The function returns a `Pooled<dyn Future<Output = T> + Send>` rather than a
+//! `Pin<Box<dyn Future<Output = T> + Send>>`, with the same `Send` and `Sync` bounds. It can be
+//! awaited and polled in the same way (`Pooled::as_mut` stands in for `Pin::as_mut`), and moved to
+//! another thread, whose free list the memory then goes back to. This option requires the `std`
+//! feature.
+//!
+//! ## Storing small futures inline
+//!
+//! The `inline = N` option returns an `Inline<dyn Future<Output = T> + Send, N>` which stores the
+//! future in an `N` byte buffer when it fits, and falls back to a `Box` when it doesn't:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(inline = 64)]
+//! async fn fib(n: u32) -> u64 {
+//!     match n {
+//!         0 | 1 => n as u64,
+//!         _ => fib(n - 1).await + fib(n - 2).await,
+//!     }
+//! }
+//! # assert!(fib(20).is_inline());
+//! ```
+//!
+//! Only the outermost call is stored inline. A future can't be stored inline in a future holding
+//! its own storage, so the calls an inlined function makes to itself (or to other inlined functions
+//! under the same attribute) are moved into a `Box` before they are awaited, which costs one more
+//! move of each of those futures than boxing them directly. The recursion therefore still allocates
+//! for every call but the outermost one. `Inline` converts into a `Pin<Box<dyn Future<Output = T> +
+//! Send>>` with `.into()` wherever one is expected. As the buffer can't move once the future is
+//! polled, `Inline` isn't `Unpin`, so pin it (e.g. with `core::pin::pin!`) to poll it by hand.
+//!
+//! ## Custom boxing strategies
+//!
+//! The `boxer = Strategy` option stores futures using an implementation of the `Boxer` trait rather
+//! than with `Box::pin`, e.g. to allocate them from an arena. The function then returns a
+//! `<Strategy as Boxer>::Pinned<dyn Future<Output = T> + Send>`:
+//!
+//! ```rust
+//! # use async_recursion::{async_recursion, BoxPin};
+//! #[async_recursion(boxer = BoxPin)]
+//! async fn fib(n: u32) -> u64 {
+//!     match n {
+//!         0 | 1 => n as u64,
+//!         _ => fib(n - 1).await + fib(n - 2).await,
+//!     }
+//! }
+//! ```
+//!
+//! `BoxPin` is the strategy used by default. With the `allocator_api` feature on a nightly compiler,
+//! `BoxPinIn<A>` stores futures with `Box::pin_in` in a default constructed allocator `A`.
+//!
+//! ## Static pools
+//!
+//! On targets without an allocator, the `static_pool = N` option stores futures in one of `N` slots of
+//! a `static` reserved for the function, each of them sized for its future at compile time. The
+//! function returns a `StaticBox<dyn Future<Output = T> + Send>`, which frees its slot once it is
+//! dropped:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(static_pool = 8)]
+//! async fn depth(input: &[u8]) -> usize {
+//!     match input.split_first() {
+//!         Some((b'[', rest)) => 1 + depth(rest).await,
+//!         _ => 0,
+//!     }
+//! }
+//! ```
+//!
+//! A call made while all the slots are in use panics, unless the function returns a `Result<T, E>`
+//! where `E: From<PoolExhausted>`, in which case it returns the error instead. The slots are shared by
+//! every thread. Functions using a static pool can't be generic or methods, and every argument must be
+//! a plain identifier. This option works without the `alloc` feature, which can be disabled to use the
+//! crate without an allocator.
+//!
+//! ## Fallible allocation
+//!
+//! `Box::pin` aborts the process when it can't allocate a future. With the `fallible` option, a
+//! function returning a `Result<T, E>` where `E: From<AllocError>` returns an error from the call
+//! instead:
+//!
+//! ```rust
+//! # use async_recursion::{async_recursion, AllocError};
+//! #[async_recursion(fallible)]
+//! async fn fib(n: u32) -> Result<u64, AllocError> {
+//!     match n {
+//!         0 | 1 => Ok(n as u64),
+//!         _ => Ok(fib(n - 1).await? + fib(n - 2).await?),
+//!     }
+//! }
+//! ```
+//!
+//! The error comes out when the returned future is awaited, as that future takes up no memory and so
+//! never needs to be allocated. Only the allocation of the function's own future is covered, not any
+//! made by `trampoline` or by the function body.
+//!
+//! ## Unrolling recursion
+//!
+//! Every call to a recursive function normally boxes a future. With `unroll = K`, the function calls
+//! `K - 1` copies of itself, nested inside each other's futures without boxing, and only the last copy
+//! boxes its calls, so that recursion allocates once every `K` levels:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(unroll = 4)]
+//! async fn countdown(n: u32) -> u32 {
+//!     if n == 0 { 0 } else { 1 + countdown(n - 1).await }
+//! }
+//! ```
+//!
+//! The function's future grows with each copy it holds, so `K` trades memory for allocations. Adding
+//! `no_alloc` never allocates at all: a call that would go deeper than `K` levels returns a
+//! [`DepthExceeded`] error converted with `From` if the function returns a `Result`, and panics
+//! otherwise, just like `max_depth`.
+//!
+//! As the function stays an `async fn`, `unroll` can't be combined with `?Send`, `Sync` or the
+//! options wrapping the returned future, such as `max_depth` or `pool`, and it can't be used on
+//! generic functions or methods. Only the calls a function makes to itself are unrolled, so it also
+//! can't be used on mutually recursive functions in a module.
+//!
+//! ## Memoization
+//!
+//! Recursive computations often end up making the same call more than once. With the `memoize`
+//! option, every call made with the same arguments during a top-level call shares a single
+//! computation, including calls which are still in flight, and gets a clone of its output:
+//!
+//! ```rust
+//! # use async_recursion::async_recursion;
+//! #[async_recursion(memoize)]
+//! async fn fib(n: u64) -> u64 {
+//!     match n {
+//!         0 | 1 => n,
+//!         _ => fib(n - 1).await + fib(n - 2).await,
+//!     }
+//! }
+//! ```
+//!
+//! The function returns a `Memoized<dyn Future<Output = T> + Send>`. Its calls are keyed by a clone
+//! of their arguments, which must be `'static` and implement `Clone`, `Hash` and `Eq`, while the
+//! output must implement `Clone`. The memo is dropped along with the top-level call, unless
+//! `memoize(global)` is used to keep it for as long as the program runs, shared by every thread.
+//! Memoized functions can't be generic or methods, every argument must be a plain identifier, and a
+//! call which awaits a call with its own arguments never completes. This option requires the `std`
+//! feature.
+//!
+//! ## Detecting cycles
+//!
+//! Walking a graph which contains a cycle recurses forever. With the `detect_cycles(key = expr)`
+//! option, each call is identified by the value of `expr`, which is evaluated from the arguments when
+//! the call is made. A call with the same key as one of the calls it is (directly or not) made from
+//! returns a `CycleDetected<K>` error carrying the keys of the calls making up the cycle, instead of
+//! recursing:
+//!
+//! ```rust
+//! # use async_recursion::{async_recursion, CycleDetected};
+//! # use std::collections::HashMap;
+//! #[async_recursion(detect_cycles(key = name.to_string()))]
+//! async fn depth(
+//!     deps: &HashMap<&str, Vec<&str>>,
+//!     name: &str,
+//! ) -> Result<usize, CycleDetected<String>> {
+//!     let mut deepest = 0;
+//!     for dep in &deps[name] {
+//!         deepest = deepest.max(1 + depth(deps, dep).await?);
+//!     }
+//!     Ok(deepest)
+//! }
+//! ```
+//!
+//! The function must return a `Result<T, E>` where `E: From<CycleDetected<K>>`, and the key must be
+//! `'static` and implement `Clone` and `PartialEq` (as well as `Send` and `Sync`, unless `?Send` is
+//! used). Only the chain of calls leading to a call is checked, so the same key can be reached more
+//! than once through different paths. The chain is captured when the call is made, so cycles are
+//! found even among calls that are polled from elsewhere, such as with `trampoline`. This option
+//! requires the `std` feature.
+//!
 //! ### License
 //!
 //! Licensed under either of
@@ -95,21 +544,102 @@
 //!
 //! at your option.
 
-extern crate proc_macro;
+#![no_std]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod boxer;
+mod coerce;
+#[cfg(feature = "std")]
+mod cycles;
+mod depth;
+#[cfg(feature = "alloc")]
+mod fallible;
+#[cfg(feature = "grow_stack")]
+mod grow_stack;
+#[cfg(feature = "alloc")]
+mod inline;
+#[cfg(feature = "std")]
+mod memoize;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod scope;
+mod static_pool;
+#[cfg(feature = "std")]
+mod trampoline;
+
+pub use async_recursion_macros::async_recursion;
+#[cfg(feature = "alloc")]
+pub use boxer::BoxPin;
+#[cfg(feature = "allocator_api")]
+pub use boxer::BoxPinIn;
+pub use boxer::Boxer;
+pub use coerce::CoerceFuture;
+#[cfg(feature = "std")]
+pub use cycles::CycleDetected;
+pub use depth::DepthExceeded;
+#[cfg(feature = "alloc")]
+pub use fallible::AllocError;
+#[cfg(feature = "alloc")]
+pub use inline::Inline;
+#[cfg(feature = "std")]
+pub use memoize::Memoized;
+#[cfg(feature = "std")]
+pub use pool::Pooled;
+pub use static_pool::{PoolExhausted, StaticBox};
+
+// Items referred to by the code generated by the macro, which aren't part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+
+    #[cfg(feature = "alloc")]
+    pub mod fallible {
+        pub use crate::fallible::try_pin;
+    }
+
+    pub mod static_pool {
+        pub use crate::static_pool::{
+            chunks_for_output, exhausted, panic_exhausted, Chunk, StaticPool,
+        };
+    }
+
+    #[cfg(feature = "std")]
+    pub mod cycles {
+        pub use crate::cycles::{find_cycle, WithCycleKey};
+    }
+
+    pub mod depth {
+        #[cfg(feature = "std")]
+        pub use crate::depth::{child_depth, WithDepth};
+        pub use crate::depth::{exceeded, panic_exceeded, panic_exceeded_future};
+    }
 
-mod expand;
-mod parse;
+    #[cfg(feature = "grow_stack")]
+    pub mod grow_stack {
+        pub use crate::grow_stack::GrowStack;
+    }
 
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::parse_macro_input;
+    #[cfg(feature = "std")]
+    pub mod memoize {
+        pub use crate::memoize::{call, Memo};
+    }
 
-#[proc_macro_attribute]
-pub fn async_recursion(args: TokenStream, input: TokenStream) -> TokenStream {
-    let mut item = parse_macro_input!(input as parse::AsyncItem);
-    let args = parse_macro_input!(args as parse::RecursionArgs);
+    #[cfg(feature = "std")]
+    pub mod pool {
+        pub use std::thread_local;
 
-    expand::expand(&mut item, &args);
+        pub use crate::pool::Pool;
+    }
 
-    TokenStream::from(quote!(#item))
+    #[cfg(feature = "std")]
+    pub mod trampoline {
+        pub use crate::trampoline::Trampoline;
+    }
 }
@@ -0,0 +1,231 @@
+use alloc::{boxed::Box, sync::Arc, task::Wake, vec::Vec};
+use core::{
+    fmt,
+    future::Future,
+    hash::Hash,
+    mem,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    boxer::{BoxPin, Boxer},
+    coerce::CoerceFuture,
+    scope::{self, Scope},
+};
+
+/// The future returned by a function annotated with `#[async_recursion(memoize)]`.
+///
+/// Every call made with the same arguments (within the same top-level call, or ever with
+/// `memoize(global)`) returns a handle to the same computation.  Whichever handle is polled drives
+/// it, and each of them completes with a clone of its output.
+pub struct Memoized<F: ?Sized + Future> {
+    call: Arc<Call<F>>,
+}
+
+struct Call<F: ?Sized + Future> {
+    state: Mutex<State<F>>,
+    wakers: Arc<Wakers>,
+}
+
+struct State<F: ?Sized + Future> {
+    // Taken out while the future is being polled, and dropped once it completes
+    future: Option<Pin<Box<F>>>,
+    output: Option<F::Output>,
+    // Whether a handle was polled while the future was being polled through another one
+    contended: bool,
+    panicked: bool,
+}
+
+// The wakers of every task waiting on a call, which are all woken whenever the future is.
+#[derive(Default)]
+struct Wakers(Mutex<Vec<Waker>>);
+
+impl Wakers {
+    fn register(&self, waker: &Waker) {
+        let mut wakers = lock(&self.0);
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+
+    fn wake_all(&self) {
+        let wakers = mem::take(&mut *lock(&self.0));
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl Wake for Wakers {
+    fn wake(self: Arc<Self>) {
+        self.wake_all();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wake_all();
+    }
+}
+
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // Nothing panics while holding a lock, but the output's `Clone` implementation might
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<F: ?Sized + Future> Memoized<F> {
+    fn new(future: Pin<Box<F>>) -> Self {
+        Self {
+            call: Arc::new(Call {
+                state: Mutex::new(State {
+                    future: Some(future),
+                    output: None,
+                    contended: false,
+                    panicked: false,
+                }),
+                wakers: Arc::default(),
+            }),
+        }
+    }
+}
+
+impl<F: ?Sized + Future> Clone for Memoized<F> {
+    fn clone(&self) -> Self {
+        Self {
+            call: self.call.clone(),
+        }
+    }
+}
+
+impl<F: ?Sized + Future> Future for Memoized<F>
+where
+    F::Output: Clone,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Marks the call as panicked if polling its future panics, so that no handle waits for it
+        struct Unwind<'a, F: ?Sized + Future>(&'a Call<F>);
+
+        impl<F: ?Sized + Future> Drop for Unwind<'_, F> {
+            fn drop(&mut self) {
+                lock(&self.0.state).panicked = true;
+                self.0.wakers.wake_all();
+            }
+        }
+
+        let call = &*self.call;
+        let mut future = {
+            let mut state = lock(&call.state);
+            if let Some(output) = &state.output {
+                return Poll::Ready(output.clone());
+            }
+            if state.panicked {
+                panic!("a memoized call with the same arguments panicked");
+            }
+
+            // Registering while holding the lock means that the output can't be stored in
+            // between, so the handle is sure to be woken
+            call.wakers.register(cx.waker());
+            match state.future.take() {
+                Some(future) => future,
+                None => {
+                    state.contended = true;
+                    return Poll::Pending;
+                }
+            }
+        };
+
+        let unwind = Unwind(call);
+        let waker = Waker::from(call.wakers.clone());
+        let poll = future.as_mut().poll(&mut Context::from_waker(&waker));
+        mem::forget(unwind);
+
+        let mut state = lock(&call.state);
+        match poll {
+            Poll::Ready(output) => {
+                drop(future);
+                state.output = Some(output.clone());
+                drop(state);
+                call.wakers.wake_all();
+                Poll::Ready(output)
+            }
+            Poll::Pending => {
+                state.future = Some(future);
+                let contended = mem::take(&mut state.contended);
+                drop(state);
+
+                // The handles polled in the meantime couldn't poll the future, so they get a
+                // chance to now, in case this one isn't polled again
+                if contended {
+                    call.wakers.wake_all();
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<F: ?Sized + Future> fmt::Debug for Memoized<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memoized").finish_non_exhaustive()
+    }
+}
+
+/// The calls made to a function annotated with `#[async_recursion(memoize)]`, keyed by their
+/// (cloned) arguments.
+pub struct Memo<K, F: ?Sized + Future> {
+    // Only created once needed, as a `HashMap` can't be created in a `static`
+    calls: Mutex<Option<HashMap<K, Memoized<F>>>>,
+}
+
+impl<K: Hash + Eq, F: ?Sized + Future> Memo<K, F> {
+    pub const fn new() -> Self {
+        Self {
+            calls: Mutex::new(None),
+        }
+    }
+
+    /// Returns the call made with `args`, or makes it with the future created by `future`.
+    pub fn get<G>(&self, args: K, future: impl FnOnce() -> G) -> Memoized<F>
+    where
+        F: CoerceFuture<G>,
+    {
+        lock(&self.calls)
+            .get_or_insert_with(HashMap::new)
+            .entry(args)
+            .or_insert_with(|| Memoized::new(BoxPin::pin(future())))
+            .clone()
+    }
+}
+
+impl<K: Hash + Eq, F: ?Sized + Future> Default for Memo<K, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Makes a call to the function identified by `key`, sharing the memo of the innermost top-level
+/// call to the same function being polled on this thread, if any.  Otherwise this is a top-level
+/// call, which gets a memo of its own for the calls it makes.
+pub fn call<K, F, G>(key: &'static u8, args: K, future: impl FnOnce() -> G) -> Memoized<F>
+where
+    K: Hash + Eq + 'static,
+    F: ?Sized + Future + CoerceFuture<G> + CoerceFuture<Scoped<K, F, G>> + 'static,
+{
+    if let Some(memo) = scope::find(key, |memo: &Arc<Memo<K, F>>| Some(memo.clone())) {
+        return memo.get(args, future);
+    }
+
+    Memoized::new(BoxPin::pin(Scope::new(
+        key,
+        Arc::new(Memo::new()),
+        future(),
+    )))
+}
+
+/// Makes the memo of a top-level call known to the calls it makes while being polled.
+pub type Scoped<K, F, G> = Scope<Arc<Memo<K, F>>, G>;
@@ -0,0 +1,190 @@
+use alloc::{
+    alloc::{alloc, dealloc, handle_alloc_error},
+    vec::Vec,
+};
+use core::{
+    alloc::Layout,
+    cell::{Cell, RefCell},
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    ptr::NonNull,
+    task::{ready, Context, Poll},
+};
+use std::thread::LocalKey;
+
+use crate::coerce::CoerceFuture;
+
+// The most blocks a pool keeps hold of, so that a single deep recursion doesn't leave a thread
+// holding on to all of its memory forever.
+const MAX_FREE_BLOCKS: usize = 1024;
+
+/// A per-thread free list of the blocks used by the futures of a function annotated with
+/// `#[async_recursion(pool)]`.
+///
+/// Every block in a pool has the same layout, that of the first future allocated from it.  The
+/// futures of a generic function may have other layouts, which are allocated as usual.
+pub struct Pool {
+    layout: Cell<Option<Layout>>,
+    free: RefCell<Vec<NonNull<u8>>>,
+}
+
+impl Pool {
+    pub const fn new() -> Self {
+        Self {
+            layout: Cell::new(None),
+            free: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn take(&self, layout: Layout) -> Option<NonNull<u8>> {
+        match self.layout.get() {
+            Some(pooled) if pooled == layout => self.free.borrow_mut().pop(),
+            Some(_) => None,
+            None => {
+                self.layout.set(Some(layout));
+                None
+            }
+        }
+    }
+
+    // Returns the block if the pool doesn't want it.
+    fn give(&self, block: NonNull<u8>, layout: Layout) -> Option<NonNull<u8>> {
+        let mut free = self.free.borrow_mut();
+        if self.layout.get() != Some(layout) || free.len() >= MAX_FREE_BLOCKS {
+            return Some(block);
+        }
+
+        free.push(block);
+        None
+    }
+}
+
+impl Default for Pool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        if let Some(layout) = self.layout.get() {
+            for block in self.free.get_mut().drain(..) {
+                // Safety: every block in the pool was allocated with its layout
+                unsafe { dealloc(block.as_ptr(), layout) };
+            }
+        }
+    }
+}
+
+/// The future returned by a function annotated with `#[async_recursion(pool)]`.
+///
+/// Like `Pin<Box<F>>`, it owns a pinned future stored on the heap, except that its memory comes
+/// from (and goes back to) a free list kept by each thread for each annotated function.  The
+/// memory is handed back as soon as the future completes, or when it is dropped.
+pub struct Pooled<F: ?Sized> {
+    // `None` once the future has completed
+    future: Option<NonNull<F>>,
+    pool: &'static LocalKey<Pool>,
+    _owned: PhantomData<F>,
+}
+
+impl<F: ?Sized> Pooled<F> {
+    #[doc(hidden)]
+    pub fn new<G>(pool: &'static LocalKey<Pool>, future: G) -> Self
+    where
+        F: CoerceFuture<G>,
+    {
+        let layout = Layout::new::<G>();
+        let block = if layout.size() == 0 {
+            NonNull::<G>::dangling()
+        } else {
+            let block = pool
+                .try_with(|pool| pool.take(layout))
+                .ok()
+                .flatten()
+                // Safety: the layout has a non-zero size
+                .or_else(|| NonNull::new(unsafe { alloc(layout) }))
+                .unwrap_or_else(|| handle_alloc_error(layout));
+            block.cast::<G>()
+        };
+
+        // Safety: the block is valid for writes of a `G`, and `coerce` returns the same pointer
+        unsafe {
+            block.as_ptr().write(future);
+            Self {
+                future: Some(NonNull::new_unchecked(F::coerce(block.as_ptr()))),
+                pool,
+                _owned: PhantomData,
+            }
+        }
+    }
+
+    /// Gets a pinned mutable reference to the future, like `Pin::as_mut` does for `Pin<Box<F>>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future has already completed, as it has been dropped.
+    pub fn as_mut(&mut self) -> Pin<&mut F> {
+        let future = self.future.expect("`async fn` resumed after completion");
+
+        // Safety: the future never moves out of its block, and is only reachable through `self`
+        unsafe { Pin::new_unchecked(&mut *future.as_ptr()) }
+    }
+
+    // Drops the future, and hands its block back to the pool.
+    fn release(&mut self) {
+        let future = match self.future.take() {
+            Some(future) => future,
+            None => return,
+        };
+
+        // Safety: the future is valid until it is dropped here, and its block was allocated with
+        // its layout (unless the layout has no size)
+        unsafe {
+            let layout = Layout::for_value(future.as_ref());
+            future.as_ptr().drop_in_place();
+
+            if layout.size() != 0 {
+                let block = future.cast::<u8>();
+                let unwanted = self
+                    .pool
+                    .try_with(|pool| pool.give(block, layout))
+                    .unwrap_or(Some(block));
+                if let Some(block) = unwanted {
+                    dealloc(block.as_ptr(), layout);
+                }
+            }
+        }
+    }
+}
+
+// Safety: `Pooled<F>` owns an `F` just like `Box<F>` does
+unsafe impl<F: ?Sized + Send> Send for Pooled<F> {}
+unsafe impl<F: ?Sized + Sync> Sync for Pooled<F> {}
+
+impl<F: ?Sized> Unpin for Pooled<F> {}
+
+impl<F: ?Sized + Future> Future for Pooled<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let output = ready!(this.as_mut().poll(cx));
+        this.release();
+        Poll::Ready(output)
+    }
+}
+
+impl<F: ?Sized> Drop for Pooled<F> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl<F: ?Sized> fmt::Debug for Pooled<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pooled").finish_non_exhaustive()
+    }
+}
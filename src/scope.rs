@@ -0,0 +1,84 @@
+use core::{
+    any::Any,
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    ptr,
+    task::{Context, Poll},
+};
+
+// The scopes being polled on the current thread, innermost first.  Each frame lives on the stack
+// of the `poll` call that pushed it.
+struct Frame {
+    key: *const u8,
+    data: *const dyn Any,
+    parent: *const Frame,
+}
+
+std::thread_local! {
+    static CURRENT: Cell<*const Frame> = const { Cell::new(ptr::null()) };
+}
+
+/// Calls `f` with the data of each scope of the function identified by `key` being polled on this
+/// thread, innermost first, until it returns `Some`.  Scopes holding another type of data are
+/// skipped.
+pub fn find<D: 'static, T>(key: &'static u8, mut f: impl FnMut(&D) -> Option<T>) -> Option<T> {
+    let mut frame = CURRENT.with(Cell::get);
+
+    // Safety: every frame in the list outlives the poll of the frames above it, and the list is
+    // only ever accessed from its own thread.
+    while let Some(fr) = unsafe { frame.as_ref() } {
+        if ptr::eq(fr.key, key) {
+            // Safety: the data is owned by the scope which pushed the frame
+            if let Some(data) = unsafe { &*fr.data }.downcast_ref::<D>() {
+                if let Some(found) = f(data) {
+                    return Some(found);
+                }
+            }
+        }
+        frame = fr.parent;
+    }
+
+    None
+}
+
+/// Makes `data` known to the calls a future makes while being polled, through [`find`].
+pub struct Scope<D, F> {
+    key: &'static u8,
+    data: D,
+    future: F,
+}
+
+impl<D, F> Scope<D, F> {
+    pub fn new(key: &'static u8, data: D, future: F) -> Self {
+        Self { key, data, future }
+    }
+}
+
+impl<D: 'static, F: Future> Future for Scope<D, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Restores the previous frame even if polling panics
+        struct Restore(*const Frame);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        // Safety: `future` is never moved out of `self`
+        let this = unsafe { self.get_unchecked_mut() };
+        let frame = Frame {
+            key: this.key,
+            data: &this.data,
+            parent: CURRENT.with(Cell::get),
+        };
+
+        let _restore = Restore(frame.parent);
+        CURRENT.with(|current| current.set(&frame));
+
+        unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx)
+    }
+}
@@ -0,0 +1,200 @@
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    future::Future,
+    mem::{self, MaybeUninit},
+    pin::Pin,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll},
+};
+
+use crate::coerce::CoerceFuture;
+
+/// The error returned by a function annotated with `#[async_recursion(static_pool = N)]` when all
+/// `N` of its slots are in use.
+///
+/// Functions returning `Result<T, E>` return this error (converted with `E::from`) instead of
+/// panicking, as long as `E: From<PoolExhausted>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolExhausted {
+    capacity: usize,
+}
+
+impl PoolExhausted {
+    /// The number of slots in the pool.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl fmt::Display for PoolExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "all {} slots of the static pool are in use",
+            self.capacity
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PoolExhausted {}
+
+pub fn exhausted(capacity: usize) -> PoolExhausted {
+    PoolExhausted { capacity }
+}
+
+#[track_caller]
+pub fn panic_exhausted(name: &str, capacity: usize) -> ! {
+    panic!("`{}` used all {} slots of its static pool", name, capacity)
+}
+
+// Slots are made up of chunks, which are aligned enough for most futures.
+const CHUNK_SIZE: usize = 16;
+
+#[repr(C, align(16))]
+pub struct Chunk([u8; CHUNK_SIZE]);
+
+/// Returns the number of chunks needed to store the future returned by `f`.
+///
+/// This is evaluated at compile time, where it fails if the future needs a greater alignment.
+pub const fn chunks_for_output<A, R, F: FnOnce(A) -> R>(_: &F) -> usize {
+    assert!(
+        mem::align_of::<R>() <= mem::align_of::<Chunk>(),
+        "the future is aligned too strictly to be stored in a static pool"
+    );
+    mem::size_of::<R>().div_ceil(CHUNK_SIZE)
+}
+
+/// A fixed number of slots, each `CHUNKS` chunks in size, which the futures of a function
+/// annotated with `#[async_recursion(static_pool = N)]` are stored in.
+pub struct StaticPool<const N: usize, const CHUNKS: usize> {
+    slots: [UnsafeCell<MaybeUninit<[Chunk; CHUNKS]>>; N],
+    used: [AtomicBool; N],
+}
+
+// Safety: a slot is only ever accessed through the `StaticBox` which claimed it
+unsafe impl<const N: usize, const CHUNKS: usize> Sync for StaticPool<N, CHUNKS> {}
+
+impl<const N: usize, const CHUNKS: usize> Default for StaticPool<N, CHUNKS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const CHUNKS: usize> StaticPool<N, CHUNKS> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            used: [const { AtomicBool::new(false) }; N],
+        }
+    }
+
+    /// Moves a future into a free slot, or hands it back if there isn't one.
+    pub fn alloc<G, F>(&'static self, future: G) -> Result<StaticBox<F>, G>
+    where
+        F: ?Sized + Future + CoerceFuture<G>,
+    {
+        const {
+            assert!(mem::size_of::<G>() <= CHUNKS * CHUNK_SIZE);
+            assert!(mem::align_of::<G>() <= mem::align_of::<Chunk>());
+        }
+
+        let free = self.used.iter().position(|used| {
+            used.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+        });
+        let i = match free {
+            Some(i) => i,
+            None => return Err(future),
+        };
+
+        let slot = self.slots[i].get().cast::<G>();
+        // Safety: the slot was just claimed, and is large enough and aligned enough for a `G`
+        unsafe {
+            slot.write(future);
+            Ok(StaticBox {
+                state: State::Slot {
+                    future: NonNull::new_unchecked(F::coerce(slot)),
+                    used: &self.used[i],
+                },
+            })
+        }
+    }
+}
+
+enum State<F: ?Sized + Future> {
+    Slot {
+        future: NonNull<F>,
+        used: &'static AtomicBool,
+    },
+    Ready(Option<F::Output>),
+}
+
+/// The future returned by a function annotated with `#[async_recursion(static_pool = N)]`.
+///
+/// Like `Pin<Box<F>>`, it owns a pinned future, except that the future is stored in one of the
+/// slots of a `static` reserved for the function, which is freed once the future is dropped.
+pub struct StaticBox<F: ?Sized + Future> {
+    state: State<F>,
+}
+
+impl<F: ?Sized + Future> StaticBox<F> {
+    /// A future which completes straight away, used to report that the pool is exhausted.
+    #[doc(hidden)]
+    pub fn ready(output: F::Output) -> Self {
+        Self {
+            state: State::Ready(Some(output)),
+        }
+    }
+
+    /// Gets a pinned mutable reference to the future, like `Pin::as_mut` does for `Pin<Box<F>>`.
+    ///
+    /// Returns `None` if the function couldn't be called, as its pool was exhausted.
+    pub fn as_mut(&mut self) -> Option<Pin<&mut F>> {
+        match self.state {
+            // Safety: the future never moves out of its slot, and is only reachable through `self`
+            State::Slot { future, .. } => {
+                Some(unsafe { Pin::new_unchecked(&mut *future.as_ptr()) })
+            }
+            State::Ready(_) => None,
+        }
+    }
+}
+
+// Safety: `StaticBox<F>` owns an `F` (or its output) just like `Box<F>` does
+unsafe impl<F: ?Sized + Future + Send> Send for StaticBox<F> where F::Output: Send {}
+unsafe impl<F: ?Sized + Future + Sync> Sync for StaticBox<F> {}
+
+impl<F: ?Sized + Future> Unpin for StaticBox<F> {}
+
+impl<F: ?Sized + Future> Future for StaticBox<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &mut this.state {
+            State::Slot { .. } => this.as_mut().expect("the future is in its slot").poll(cx),
+            State::Ready(output) => {
+                Poll::Ready(output.take().expect("`async fn` resumed after completion"))
+            }
+        }
+    }
+}
+
+impl<F: ?Sized + Future> Drop for StaticBox<F> {
+    fn drop(&mut self) {
+        if let State::Slot { future, used } = self.state {
+            // Safety: the slot holds the future until it is freed here
+            unsafe { future.as_ptr().drop_in_place() };
+            used.store(false, Ordering::Release);
+        }
+    }
+}
+
+impl<F: ?Sized + Future> fmt::Debug for StaticBox<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticBox").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,288 @@
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{
+    cell::{Cell, RefCell},
+    future::Future,
+    mem,
+    pin::Pin,
+    ptr,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    collections::HashSet,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+// A call made in `trampoline` mode, shared between its `Trampoline` and the driver polling it.
+// It is type-erased so that calls to different functions can share a driver.
+trait Frame {
+    // Polls the call, keeping hold of its output once it completes.  Returns whether it has.
+    fn poll(&self, cx: &mut Context<'_>) -> bool;
+
+    // Drops the future (or output) of the call.
+    fn cancel(&self);
+}
+
+struct Call<F: Future> {
+    // The future is pinned, as the call is never moved out of its `Arc`
+    state: Mutex<State<F>>,
+}
+
+enum State<F: Future> {
+    Running(F),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F: Future> Call<F> {
+    fn lock(&self) -> MutexGuard<'_, State<F>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn take(&self) -> Option<F::Output> {
+        let mut state = self.lock();
+        match &*state {
+            State::Running(_) => None,
+            State::Done(_) => match mem::replace(&mut *state, State::Taken) {
+                State::Done(output) => Some(output),
+                _ => unreachable!(),
+            },
+            State::Taken => panic!("`async fn` resumed after completion"),
+        }
+    }
+}
+
+impl<F: Future> Frame for Call<F> {
+    fn poll(&self, cx: &mut Context<'_>) -> bool {
+        let mut state = self.lock();
+        if let State::Running(future) = &mut *state {
+            // Safety: the future is never moved, only dropped in place once it completes
+            match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                Poll::Ready(output) => *state = State::Done(output),
+                Poll::Pending => return false,
+            }
+        }
+
+        true
+    }
+
+    fn cancel(&self) {
+        // The future is dropped in place
+        *self.lock() = State::Taken;
+    }
+}
+
+std::thread_local! {
+    // The calls waiting to be cancelled by the outermost `cancel` running on this thread, if any.
+    static TEARDOWN: RefCell<Option<Vec<Arc<dyn Frame>>>> = const { RefCell::new(None) };
+}
+
+// Cancels a call whose `Trampoline` is being dropped.  Dropping its future drops the
+// `Trampoline`s of the calls it is waiting on, which are cancelled in turn from a loop rather than
+// from inside the `drop`, so that dropping a deep recursion doesn't overflow the stack.
+fn cancel(call: Arc<dyn Frame>) {
+    // Drops whatever is left if cancelling a call panics
+    struct Finish;
+
+    impl Drop for Finish {
+        fn drop(&mut self) {
+            // The calls are dropped once the list is no longer borrowed
+            let rest = TEARDOWN.with(|teardown| teardown.borrow_mut().take());
+            drop(rest);
+        }
+    }
+
+    // A call dropped while the thread is being torn down is simply dropped recursively
+    let outermost = TEARDOWN
+        .try_with(|teardown| match &mut *teardown.borrow_mut() {
+            Some(calls) => {
+                calls.push(call);
+                None
+            }
+            teardown @ None => {
+                *teardown = Some(Vec::new());
+                Some(call)
+            }
+        })
+        .unwrap_or(None);
+
+    let Some(mut call) = outermost else {
+        return;
+    };
+    let _finish = Finish;
+    loop {
+        call.cancel();
+        drop(call);
+
+        match TEARDOWN.with(|teardown| teardown.borrow_mut().as_mut().and_then(Vec::pop)) {
+            Some(next) => call = next,
+            None => break,
+        }
+    }
+}
+
+struct Node {
+    // Only the `Trampoline` of a call keeps it alive, so a call is never polled once dropped
+    call: Weak<dyn Frame>,
+    // The node of the call that was being polled when this one was handed over
+    caller: Option<usize>,
+}
+
+/// Polls the calls of a recursion one at a time from a loop, instead of each call polling the
+/// calls it makes from inside its own `poll`.
+///
+/// A driver only lasts for a single poll of the outermost call.  A call that is polled while a
+/// driver is running isn't polled there and then, but handed over to the driver, which polls it
+/// once its caller has returned.  Only the calls polled by their caller during this round are
+/// polled, and none of them is held on to afterwards.
+struct Driver {
+    waker: Waker,
+    nodes: RefCell<Vec<Node>>,
+    // The addresses of the calls handed over during this round, which can't be reused by another
+    // call as the nodes hold on to their allocations
+    requested: RefCell<HashSet<*const ()>>,
+    // The nodes waiting to be polled, innermost last
+    pending: RefCell<Vec<usize>>,
+    polling: Cell<Option<usize>>,
+}
+
+std::thread_local! {
+    // The driver polling calls on the current thread, if any.
+    static CURRENT: Cell<*const Driver> = const { Cell::new(ptr::null()) };
+}
+
+impl Driver {
+    // Polls `root` and every call it hands over, until none of them can make any progress.
+    fn run(root: &Arc<dyn Frame>, waker: &Waker) {
+        // Restores the previous driver even if polling panics
+        struct Restore(*const Driver);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let driver = Driver {
+            waker: waker.clone(),
+            nodes: RefCell::default(),
+            requested: RefCell::default(),
+            pending: RefCell::default(),
+            polling: Cell::new(None),
+        };
+        driver.push(root, None);
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(&driver)));
+        let mut cx = Context::from_waker(&driver.waker);
+
+        loop {
+            let Some(node) = driver.pending.borrow_mut().pop() else {
+                break;
+            };
+            let (call, caller) = {
+                let nodes = driver.nodes.borrow();
+                (nodes[node].call.upgrade(), nodes[node].caller)
+            };
+            let Some(call) = call else {
+                continue;
+            };
+
+            driver.polling.set(Some(node));
+            let ready = call.poll(&mut cx);
+            driver.polling.set(None);
+
+            // The caller can now take the output
+            if let (true, Some(caller)) = (ready, caller) {
+                driver.pending.borrow_mut().push(caller);
+            }
+        }
+    }
+
+    fn push(&self, call: &Arc<dyn Frame>, caller: Option<usize>) {
+        if !self.requested.borrow_mut().insert(Arc::as_ptr(call).cast()) {
+            // It is already waiting to be polled, or was polled and is waiting on something else
+            return;
+        }
+
+        let mut nodes = self.nodes.borrow_mut();
+        self.pending.borrow_mut().push(nodes.len());
+        nodes.push(Node {
+            call: Arc::downgrade(call),
+            caller,
+        });
+    }
+
+    // Hands a call over to the driver running on this thread, if the call is being polled on its
+    // behalf.  Returns whether it was.
+    fn hand_over(call: &Arc<dyn Frame>, waker: &Waker) -> bool {
+        // Safety: a driver is only current while `run` is polling
+        let Some(driver) = (unsafe { CURRENT.with(Cell::get).as_ref() }) else {
+            return false;
+        };
+
+        // A call polled with another waker (e.g. by a combinator waking each of its futures
+        // separately) can't rely on the driver being woken for it
+        let Some(caller) = driver.polling.get() else {
+            return false;
+        };
+        if !driver.waker.will_wake(waker) {
+            return false;
+        }
+
+        driver.push(call, Some(caller));
+        true
+    }
+}
+
+/// Wraps the future of a call made in `trampoline` mode.
+///
+/// Polling it from a call that is itself being polled by a driver hands it over to the driver,
+/// which polls it from a loop once the caller has returned, so the depth of the native stack
+/// doesn't depend on the depth of the recursion.  Any other poll sets up a driver of its own.
+///
+/// The driver polls calls after their callers' `poll` has returned, which is only sound because
+/// they can't borrow anything from them, hence the `'static` bound.
+pub struct Trampoline<F: Future + 'static> {
+    call: Arc<Call<F>>,
+}
+
+impl<F: Future + 'static> Trampoline<F> {
+    pub fn new(future: F) -> Self {
+        Self {
+            call: Arc::new(Call {
+                state: Mutex::new(State::Running(future)),
+            }),
+        }
+    }
+}
+
+impl<F: Future + 'static> Unpin for Trampoline<F> {}
+
+impl<F: Future + 'static> Future for Trampoline<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(output) = self.call.take() {
+            return Poll::Ready(output);
+        }
+
+        let call: Arc<dyn Frame> = self.call.clone();
+        if Driver::hand_over(&call, cx.waker()) {
+            return Poll::Pending;
+        }
+
+        Driver::run(&call, cx.waker());
+        match self.call.take() {
+            Some(output) => Poll::Ready(output),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<F: Future + 'static> Drop for Trampoline<F> {
+    fn drop(&mut self) {
+        cancel(self.call.clone());
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "allocator_api")]
+#![feature(allocator_api)]
+
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use async_recursion::{async_recursion, BoxPinIn};
+use futures_executor::block_on;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts its allocations, and otherwise defers to the global allocator.
+#[derive(Default)]
+struct Counting;
+
+unsafe impl Allocator for Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[async_recursion(boxer = BoxPinIn<Counting>)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => n as u64,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[test]
+fn box_pin_in_allocator() {
+    assert_eq!(block_on(fib(10)), 55);
+    // One allocation per call
+    assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), 177);
+}
@@ -1,6 +1,6 @@
 use async_recursion::async_recursion;
 
-#[async_recursion(Sync)]
+#[async_recursion(Sync, allow_nonrecursive)]
 async fn send_and_sync() {}
 
 fn assert_is_send_and_sync(_: impl Send + Sync) {}
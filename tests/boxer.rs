@@ -0,0 +1,152 @@
+use std::{
+    alloc::Layout,
+    cell::Cell,
+    future::Future,
+    mem::MaybeUninit,
+    pin::Pin,
+    ptr::NonNull,
+    task::{Context, Poll},
+};
+
+use async_recursion::{async_recursion, BoxPin, Boxer, CoerceFuture};
+use futures_executor::block_on;
+
+/// A bump arena, which is reset once every future allocated from it has been dropped.
+struct Arena {
+    bytes: Box<[MaybeUninit<u8>]>,
+    used: Cell<usize>,
+    live: Cell<usize>,
+}
+
+impl Arena {
+    fn alloc(&self, layout: Layout) -> *mut u8 {
+        let start = self.bytes.as_ptr() as usize;
+        let offset = (start + self.used.get()).next_multiple_of(layout.align()) - start;
+        assert!(
+            offset + layout.size() <= self.bytes.len(),
+            "arena exhausted"
+        );
+
+        self.used.set(offset + layout.size());
+        self.live.set(self.live.get() + 1);
+        unsafe { self.bytes.as_ptr().add(offset) as *mut u8 }
+    }
+
+    fn release(&self) {
+        self.live.set(self.live.get() - 1);
+        if self.live.get() == 0 {
+            self.used.set(0);
+        }
+    }
+}
+
+thread_local! {
+    static ARENA: Arena = Arena {
+        bytes: vec![MaybeUninit::uninit(); 1 << 20].into_boxed_slice(),
+        used: Cell::new(0),
+        live: Cell::new(0),
+    };
+}
+
+fn arena_used() -> usize {
+    ARENA.with(|arena| arena.used.get())
+}
+
+/// A future allocated from the current thread's arena.
+struct ArenaBox<F: ?Sized>(NonNull<F>);
+
+impl<F: ?Sized + Future> Future for ArenaBox<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { Pin::new_unchecked(&mut *self.0.as_ptr()) }.poll(cx)
+    }
+}
+
+impl<F: ?Sized> Drop for ArenaBox<F> {
+    fn drop(&mut self) {
+        unsafe { self.0.as_ptr().drop_in_place() };
+        ARENA.with(Arena::release);
+    }
+}
+
+struct InArena;
+
+impl Boxer for InArena {
+    type Pinned<F: ?Sized + Future> = ArenaBox<F>;
+
+    fn pin<G, F: ?Sized + Future + CoerceFuture<G>>(future: G) -> Self::Pinned<F> {
+        let ptr = ARENA.with(|arena| arena.alloc(Layout::new::<G>())) as *mut G;
+        unsafe {
+            ptr.write(future);
+            ArenaBox(NonNull::new_unchecked(F::coerce(ptr)))
+        }
+    }
+}
+
+#[async_recursion(?Send, boxer = InArena)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => n as u64,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[async_recursion(?Send, boxer = InArena)]
+async fn count_words(text: &str) -> usize {
+    match text.trim_start().split_once(' ') {
+        Some((_, rest)) => 1 + count_words(rest).await,
+        None => !text.trim().is_empty() as usize,
+    }
+}
+
+struct Tree(Vec<Tree>);
+
+impl Tree {
+    #[async_recursion(?Send, boxer = InArena)]
+    async fn size(&self) -> usize {
+        let mut size = 1;
+        for child in &self.0 {
+            size += child.size().await;
+        }
+        size
+    }
+}
+
+#[async_recursion(Sync, boxer = BoxPin)]
+async fn sum(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        n + sum(n - 1).await
+    }
+}
+
+#[test]
+fn boxer_allocates_from_arena() {
+    let future = fib(10);
+    assert!(arena_used() > 0);
+    assert_eq!(block_on(future), 55);
+    assert_eq!(arena_used(), 0);
+}
+
+#[test]
+fn boxer_borrowed_arguments() {
+    assert_eq!(block_on(count_words("the quick brown fox")), 4);
+    assert_eq!(block_on(count_words("")), 0);
+
+    let tree = Tree(vec![Tree(vec![]), Tree(vec![Tree(vec![])])]);
+    assert_eq!(block_on(tree.size()), 4);
+    assert_eq!(arena_used(), 0);
+}
+
+#[test]
+fn boxer_bounds() {
+    fn assert_send<T: Send>(_: &T) {}
+    fn assert_sync<T: Sync>(_: &T) {}
+
+    let future: Pin<Box<dyn Future<Output = u64> + Send + Sync>> = sum(10);
+    assert_send(&future);
+    assert_sync(&future);
+    assert_eq!(block_on(future), 55);
+}
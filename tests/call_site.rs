@@ -0,0 +1,67 @@
+use std::rc::Rc;
+
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+#[async_recursion(call_site)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 => panic!("zero is not a valid argument to fib()!"),
+        1 | 2 => 1,
+        3 => 2,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[async_recursion(call_site)]
+async fn count_rc(counter: Rc<u32>) -> u32 {
+    if *counter == 0 {
+        0
+    } else {
+        1 + count_rc(Rc::new(*counter - 1)).await
+    }
+}
+
+struct Walker;
+
+#[async_recursion(call_site)]
+impl Walker {
+    async fn is_even(&self, n: u32) -> bool {
+        n == 0 || self.is_odd(n - 1).await
+    }
+
+    async fn is_odd(&self, n: u32) -> bool {
+        n != 0 && Self::is_even(self, n - 1).await
+    }
+}
+
+#[async_recursion(call_site)]
+mod parity {
+    pub async fn is_even(n: u32) -> bool {
+        n == 0 || is_odd(n - 1).await
+    }
+
+    pub async fn is_odd(n: u32) -> bool {
+        n != 0 && is_even(n - 1).await
+    }
+}
+
+#[test]
+fn call_site_boxing_works() {
+    block_on(async move {
+        assert_eq!(fib(6).await, 8);
+        assert_eq!(count_rc(Rc::new(5)).await, 5);
+        assert!(Walker.is_even(10).await);
+        assert!(!Walker.is_odd(10).await);
+        assert!(parity::is_odd(7).await);
+    });
+}
+
+#[test]
+fn call_site_futures_leak_auto_traits() {
+    fn assert_is_send(_: impl Send) {}
+
+    assert_is_send(fib(6));
+    assert_is_send(Walker.is_even(4));
+    assert_is_send(parity::is_even(4));
+}
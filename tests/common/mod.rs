@@ -0,0 +1,58 @@
+//! Fixtures shared by the tests of several options.  Each test uses only some of them.
+#![allow(dead_code)]
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+/// Returns `Pending` the first time it is polled.
+pub struct YieldNow(bool);
+
+pub fn yield_now() -> YieldNow {
+    YieldNow(false)
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// Runs `f` on a thread with a stack far too small for a deep recursion to poll (or drop) its
+/// calls from inside each other.
+pub fn with_small_stack<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    std::thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(f)
+        .unwrap()
+        .join()
+        .unwrap()
+}
+
+/// Checks that dropping a pending call to `hold`, which makes `n` nested calls each holding a
+/// clone of `token` and then never completes, drops every call it is waiting on.
+pub fn assert_drops_pending_calls<F: Future<Output = ()>>(
+    n: u32,
+    hold: impl FnOnce(u32, Arc<()>) -> F,
+) {
+    let token = Arc::new(());
+    let mut future = Box::pin(hold(n, token.clone()));
+
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(Arc::strong_count(&token), n as usize + 2);
+
+    drop(future);
+    assert_eq!(Arc::strong_count(&token), 1);
+}
@@ -0,0 +1,33 @@
+use futures_executor::block_on;
+
+mod facade {
+    pub use async_recursion as reexported;
+}
+
+mod user {
+    use crate::facade::reexported::async_recursion;
+
+    #[async_recursion(crate = crate::facade::reexported)]
+    pub async fn fib(n: u32) -> u64 {
+        match n {
+            0 | 1 => 1,
+            _ => fib(n - 1).await + fib(n - 2).await,
+        }
+    }
+
+    #[async_recursion(call_site, crate = crate::facade::reexported)]
+    pub async fn fib_call_site(n: u32) -> u64 {
+        match n {
+            0 | 1 => 1,
+            _ => fib_call_site(n - 1).await + fib_call_site(n - 2).await,
+        }
+    }
+}
+
+#[test]
+fn crate_path_works() {
+    block_on(async move {
+        assert_eq!(user::fib(10).await, 89);
+        assert_eq!(user::fib_call_site(10).await, 89);
+    });
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use async_recursion::{async_recursion, CycleDetected};
+use futures_executor::block_on;
+
+#[derive(Debug, PartialEq)]
+enum ResolveError {
+    Cycle(Vec<String>),
+    Missing(String),
+}
+
+impl From<CycleDetected<String>> for ResolveError {
+    fn from(err: CycleDetected<String>) -> Self {
+        ResolveError::Cycle(err.into_path())
+    }
+}
+
+struct Registry {
+    dependencies: HashMap<&'static str, Vec<&'static str>>,
+}
+
+impl Registry {
+    fn new(dependencies: &[(&'static str, &[&'static str])]) -> Self {
+        Self {
+            dependencies: dependencies
+                .iter()
+                .map(|(name, deps)| (*name, deps.to_vec()))
+                .collect(),
+        }
+    }
+
+    /// Lists the packages `name` depends on, directly or not, in the order they are reached.
+    #[async_recursion(detect_cycles(key = name.to_string()))]
+    async fn resolve(&self, name: &str) -> Result<Vec<String>, ResolveError> {
+        let deps = self
+            .dependencies
+            .get(name)
+            .ok_or_else(|| ResolveError::Missing(name.to_string()))?;
+
+        let mut resolved = vec![];
+        for dep in deps {
+            resolved.push(dep.to_string());
+            resolved.extend(self.resolve(dep).await?);
+        }
+        Ok(resolved)
+    }
+}
+
+#[async_recursion(detect_cycles(key = n % 5))]
+async fn wrap_around(n: u32) -> Result<u32, CycleDetected<u32>> {
+    if n == 0 {
+        Ok(0)
+    } else {
+        Ok(1 + wrap_around(n - 1).await?)
+    }
+}
+
+#[async_recursion(trampoline, detect_cycles(key = node))]
+async fn trampolined_walk(node: u32) -> Result<u32, CycleDetected<u32>> {
+    Ok(1 + trampolined_walk((node + 1) % 3).await?)
+}
+
+fn path(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn detect_cycles_allows_acyclic_graphs() {
+    // `d` is reached twice, but never from itself
+    let registry = Registry::new(&[("a", &["b", "c"]), ("b", &["d"]), ("c", &["d"]), ("d", &[])]);
+
+    for _ in 0..3 {
+        assert_eq!(
+            block_on(registry.resolve("a")),
+            Ok(path(&["b", "d", "c", "d"]))
+        );
+    }
+    assert_eq!(
+        block_on(wrap_around(4)),
+        Ok(4),
+        "keys only repeat after five calls"
+    );
+}
+
+#[test]
+fn detect_cycles_returns_the_cycle() {
+    let registry = Registry::new(&[
+        ("app", &["a"]),
+        ("a", &["b"]),
+        ("b", &["c"]),
+        ("c", &["a"]),
+        ("self", &["self"]),
+    ]);
+
+    assert_eq!(
+        block_on(registry.resolve("app")),
+        Err(ResolveError::Cycle(path(&["a", "b", "c", "a"])))
+    );
+    assert_eq!(
+        block_on(registry.resolve("self")),
+        Err(ResolveError::Cycle(path(&["self", "self"])))
+    );
+}
+
+#[test]
+fn detect_cycles_reports_the_path() {
+    let err = block_on(wrap_around(7)).unwrap_err();
+    assert_eq!(err.path(), [2, 1, 0, 4, 3, 2]);
+    assert_eq!(
+        err.to_string(),
+        "recursion cycle detected: 2 -> 1 -> 0 -> 4 -> 3 -> 2"
+    );
+}
+
+#[test]
+fn detect_cycles_through_trampolined_calls() {
+    // The calls are polled from the trampoline's loop, so their callers aren't being polled then
+    let err = block_on(trampolined_walk(0)).unwrap_err();
+    assert_eq!(err.path(), [0, 1, 2, 0]);
+}
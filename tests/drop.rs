@@ -0,0 +1,53 @@
+mod common;
+
+use std::{future::Future, sync::Arc, task::Poll};
+
+use async_recursion::async_recursion;
+use common::{with_small_stack, yield_now};
+use futures_executor::block_on;
+
+const DEPTH: u32 = 1_000_000;
+
+#[async_recursion(trampoline)]
+async fn hold(n: u32, token: Arc<()>) {
+    if n == 0 {
+        std::future::pending::<()>().await;
+    } else {
+        hold(n - 1, token.clone()).await;
+    }
+}
+
+/// Polls `future` until `deadline` completes, dropping it if it hasn't completed by then.
+async fn timeout<F: Future, D: Future>(future: F, deadline: D) -> Option<F::Output> {
+    let (mut future, mut deadline) = (Box::pin(future), Box::pin(deadline));
+
+    std::future::poll_fn(|cx| {
+        if let Poll::Ready(out) = future.as_mut().poll(cx) {
+            Poll::Ready(Some(out))
+        } else if deadline.as_mut().poll(cx).is_ready() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+#[async_recursion(trampoline, allow_nonrecursive)]
+async fn hold_until_deadline(n: u32, token: Arc<()>) -> bool {
+    timeout(hold(n, token), yield_now()).await.is_none()
+}
+
+#[test]
+fn drop_deep_recursion() {
+    with_small_stack(|| common::assert_drops_pending_calls(DEPTH, hold));
+}
+
+#[test]
+fn drop_deep_recursion_from_caller() {
+    with_small_stack(|| {
+        let token = Arc::new(());
+        assert!(block_on(hold_until_deadline(DEPTH, token.clone())));
+        assert_eq!(Arc::strong_count(&token), 1);
+    });
+}
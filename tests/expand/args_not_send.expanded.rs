@@ -1,5 +1,7 @@
 use async_recursion::async_recursion;
 #[must_use]
-fn no_send_bound() -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = ()>>> {
-    Box::pin(async move {})
+fn no_send_bound() -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<dyn ::core::future::Future<Output = ()>>,
+> {
+    ::async_recursion::__private::Box::pin(async move {})
 }
@@ -1,4 +1,4 @@
 use async_recursion::async_recursion;
 
-#[async_recursion(?Send)]
+#[async_recursion(?Send, allow_nonrecursive)]
 async fn no_send_bound() {}
\ No newline at end of file
@@ -1,13 +1,13 @@
 use async_recursion::async_recursion;
 
-#[async_recursion(?Send, Sync)]
+#[async_recursion(?Send, Sync, allow_nonrecursive)]
 async fn not_send_sync_1() {}
 
-#[async_recursion(?Send,Sync)]
+#[async_recursion(?Send,Sync, allow_nonrecursive)]
 async fn not_send_sync_2() {}
 
-#[async_recursion(Sync, ?Send)]
+#[async_recursion(Sync, ?Send, allow_nonrecursive)]
 async fn sync_not_send_1() {}
 
-#[async_recursion(Sync,?Send)]
+#[async_recursion(Sync,?Send, allow_nonrecursive)]
 async fn sync_not_send_2() {}
\ No newline at end of file
@@ -1,11 +1,11 @@
 use async_recursion::async_recursion;
 #[must_use]
 fn sync() -> ::core::pin::Pin<
-    Box<
+    ::async_recursion::__private::Box<
         dyn ::core::future::Future<
             Output = (),
         > + ::core::marker::Send + ::core::marker::Sync,
     >,
 > {
-    Box::pin(async move {})
+    ::async_recursion::__private::Box::pin(async move {})
 }
@@ -1,4 +1,4 @@
 use async_recursion::async_recursion;
 
-#[async_recursion(Sync)]
+#[async_recursion(Sync, allow_nonrecursive)]
 async fn sync() {}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use async_recursion::async_recursion;
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => 1,
+        _ => {
+            ::async_recursion::__private::Box::pin(fib(n - 1)).await
+                + ::async_recursion::__private::Box::pin(self::fib(n - 2)).await
+        }
+    }
+}
+struct S;
+impl S {
+    async fn countdown(&self, n: u32) -> u32 {
+        if n == 0 {
+            0
+        } else {
+            ::async_recursion::__private::Box::pin(self.countdown(n - 1)).await
+                + ::async_recursion::__private::Box::pin(Self::countdown(self, 0)).await
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(call_site)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => 1,
+        _ => fib(n - 1).await + self::fib(n - 2).await,
+    }
+}
+
+struct S;
+
+impl S {
+    #[async_recursion(call_site)]
+    async fn countdown(&self, n: u32) -> u32 {
+        if n == 0 {
+            0
+        } else {
+            self.countdown(n - 1).await + Self::countdown(self, 0).await
+        }
+    }
+}
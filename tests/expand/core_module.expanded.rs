@@ -3,7 +3,9 @@ use async_recursion::async_recursion;
 pub fn n(
     x: i32,
 ) -> ::core::pin::Pin<
-    Box<dyn ::core::future::Future<Output = i32> + ::core::marker::Send>,
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = i32> + ::core::marker::Send,
+    >,
 > {
-    Box::pin(async move { x })
+    ::async_recursion::__private::Box::pin(async move { x })
 }
@@ -1,6 +1,6 @@
 use async_recursion::async_recursion;
 
-#[async_recursion]
+#[async_recursion(allow_nonrecursive)]
 pub async fn n(x: i32) -> i32 {
     x
 }
@@ -0,0 +1,16 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn fib(
+    n: u32,
+) -> ::core::pin::Pin<
+    ::my_facade::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+    >,
+> {
+    ::my_facade::async_recursion::__private::Box::pin(async move {
+        match n {
+            0 | 1 => 1,
+            _ => fib(n - 1).await + fib(n - 2).await,
+        }
+    })
+}
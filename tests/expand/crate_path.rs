@@ -0,0 +1,9 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(crate = ::my_facade::async_recursion)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => 1,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
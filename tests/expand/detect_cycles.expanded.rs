@@ -0,0 +1,31 @@
+use async_recursion::{async_recursion, CycleDetected};
+#[must_use]
+fn walk(
+    node: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = Result<u32, CycleDetected<u32>>,
+        > + ::core::marker::Send,
+    >,
+> {
+    static __ASYNC_RECURSION_CYCLES: u8 = 0;
+    let (__async_recursion_key, __async_recursion_cycle) = ::async_recursion::__private::cycles::find_cycle(
+        &__ASYNC_RECURSION_CYCLES,
+        node,
+    );
+    ::async_recursion::__private::Box::pin(
+        ::async_recursion::__private::cycles::WithCycleKey::new(
+            &__ASYNC_RECURSION_CYCLES,
+            __async_recursion_key,
+            async move {
+                if let ::core::option::Option::Some(__async_recursion_cycle) = __async_recursion_cycle {
+                    return ::core::result::Result::Err(
+                        ::core::convert::From::from(__async_recursion_cycle),
+                    );
+                }
+                if node == 0 { Ok(0) } else { walk(node / 2).await }
+            },
+        ),
+    )
+}
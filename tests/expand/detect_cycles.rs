@@ -0,0 +1,6 @@
+use async_recursion::{async_recursion, CycleDetected};
+
+#[async_recursion(detect_cycles(key = node))]
+async fn walk(node: u32) -> Result<u32, CycleDetected<u32>> {
+    if node == 0 { Ok(0) } else { walk(node / 2).await }
+}
@@ -0,0 +1,18 @@
+use async_recursion::{async_recursion, AllocError};
+#[must_use]
+fn fib(
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = Result<u64, AllocError>,
+        > + ::core::marker::Send,
+    >,
+> {
+    ::async_recursion::__private::fallible::try_pin(async move {
+        match n {
+            0 | 1 => Ok(n as u64),
+            _ => Ok(fib(n - 1).await? + fib(n - 2).await?),
+        }
+    })
+}
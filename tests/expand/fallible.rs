@@ -0,0 +1,9 @@
+use async_recursion::{async_recursion, AllocError};
+
+#[async_recursion(fallible)]
+async fn fib(n: u32) -> Result<u64, AllocError> {
+    match n {
+        0 | 1 => Ok(n as u64),
+        _ => Ok(fib(n - 1).await? + fib(n - 2).await?),
+    }
+}
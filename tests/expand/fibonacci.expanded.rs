@@ -3,9 +3,11 @@ use async_recursion::async_recursion;
 fn fib(
     n: u32,
 ) -> ::core::pin::Pin<
-    Box<dyn ::core::future::Future<Output = u64> + ::core::marker::Send>,
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+    >,
 > {
-    Box::pin(async move {
+    ::async_recursion::__private::Box::pin(async move {
         match n {
             0 => {
                 ::std::rt::begin_panic("zero is not a valid argument to fib()!");
@@ -3,7 +3,7 @@ use async_recursion::async_recursion;
 pub fn generic_parameter<'async_recursion, S: Marker + Send>(
     mut x: S,
 ) -> ::core::pin::Pin<
-    Box<
+    ::async_recursion::__private::Box<
         dyn ::core::future::Future<
             Output = u64,
         > + 'async_recursion + ::core::marker::Send,
@@ -12,5 +12,7 @@ pub fn generic_parameter<'async_recursion, S: Marker + Send>(
 where
     S: 'async_recursion,
 {
-    Box::pin(async move { if x.descend() { generic_parameter(x).await } else { 0 } })
+    ::async_recursion::__private::Box::pin(async move {
+        if x.descend() { generic_parameter(x).await } else { 0 }
+    })
 }
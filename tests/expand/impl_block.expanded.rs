@@ -0,0 +1,47 @@
+use async_recursion::async_recursion;
+struct S;
+impl S {
+    const N: u64 = 3;
+    fn not_async(&self) -> u64 {
+        Self::N
+    }
+    #[must_use]
+    fn countdown<'__async_recursion_life_self, 'async_recursion>(
+        &'__async_recursion_life_self self,
+        n: u64,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<
+                Output = u64,
+            > + 'async_recursion + ::core::marker::Send,
+        >,
+    >
+    where
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion,
+    {
+        ::async_recursion::__private::Box::pin(async move {
+            if n == 0 { self.not_async() } else { self.countdown(n - 1).await }
+        })
+    }
+    #[must_use]
+    fn not_send<
+        '__async_recursion_life0,
+        '__async_recursion_life_self,
+        'async_recursion,
+    >(
+        &'__async_recursion_life_self self,
+        x: &'__async_recursion_life0 u64,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<Output = u64> + 'async_recursion,
+        >,
+    >
+    where
+        '__async_recursion_life0: 'async_recursion,
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion,
+    {
+        ::async_recursion::__private::Box::pin(async move { *x })
+    }
+}
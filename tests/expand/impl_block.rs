@@ -0,0 +1,25 @@
+use async_recursion::async_recursion;
+
+struct S;
+
+#[async_recursion]
+impl S {
+    const N: u64 = 3;
+
+    fn not_async(&self) -> u64 {
+        Self::N
+    }
+
+    async fn countdown(&self, n: u64) -> u64 {
+        if n == 0 {
+            self.not_async()
+        } else {
+            self.countdown(n - 1).await
+        }
+    }
+
+    #[async_recursion(?Send, allow_nonrecursive)]
+    async fn not_send(&self, x: &u64) -> u64 {
+        *x
+    }
+}
@@ -0,0 +1,21 @@
+use async_recursion::async_recursion;
+struct Parser<'a, T> {
+    input: &'a [T],
+}
+impl<'a, T: Sync> Parser<'a, T> {
+    #[must_use]
+    fn parse<'async_recursion>(
+        self,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<
+                Output = usize,
+            > + 'async_recursion + ::core::marker::Send,
+        >,
+    >
+    where
+        Self: 'async_recursion,
+    {
+        ::async_recursion::__private::Box::pin(async move { self.input.len() })
+    }
+}
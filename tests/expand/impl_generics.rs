@@ -0,0 +1,12 @@
+use async_recursion::async_recursion;
+
+struct Parser<'a, T> {
+    input: &'a [T],
+}
+
+impl<'a, T: Sync> Parser<'a, T> {
+    #[async_recursion(allow_nonrecursive)]
+    async fn parse(self) -> usize {
+        self.input.len()
+    }
+}
@@ -0,0 +1,44 @@
+use async_recursion::async_recursion;
+struct Node {
+    children: Vec<Node>,
+}
+#[must_use]
+fn walk<'__async_recursion_life0, '__async_recursion_life1, 'async_recursion>(
+    node: &'__async_recursion_life0 Node,
+    visit: &'__async_recursion_life1 mut (impl FnMut(&Node) + Send + 'async_recursion),
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = ()> + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        visit(node);
+        for child in &node.children {
+            walk(child, visit).await;
+        }
+    })
+}
+#[must_use]
+fn sum_all<'__async_recursion_life0, 'async_recursion>(
+    values: impl Iterator<
+        Item = &'__async_recursion_life0 u32,
+    > + Send + 'async_recursion,
+    depth: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = u32,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    '__async_recursion_life0: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if depth == 0 { values.sum() } else { sum_all(values, depth - 1).await }
+    })
+}
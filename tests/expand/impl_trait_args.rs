@@ -0,0 +1,18 @@
+use async_recursion::async_recursion;
+
+struct Node {
+    children: Vec<Node>,
+}
+
+#[async_recursion]
+async fn walk(node: &Node, visit: &mut (impl FnMut(&Node) + Send)) {
+    visit(node);
+    for child in &node.children {
+        walk(child, visit).await;
+    }
+}
+
+#[async_recursion]
+async fn sum_all(values: impl Iterator<Item = &u32> + Send, depth: u32) -> u32 {
+    if depth == 0 { values.sum() } else { sum_all(values, depth - 1).await }
+}
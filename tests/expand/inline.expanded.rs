@@ -0,0 +1,22 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn fib(
+    n: u32,
+) -> ::async_recursion::Inline<
+    dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+    128usize,
+> {
+    ::async_recursion::Inline::new(async move {
+        match n {
+            0 => {
+                ::std::rt::begin_panic("zero is not a valid argument to fib()!");
+            }
+            1 | 2 => 1,
+            3 => 2,
+            _ => {
+                ::async_recursion::Inline::into_boxed(fib(n - 1)).await
+                    + ::async_recursion::Inline::into_boxed(fib(n - 2)).await
+            }
+        }
+    })
+}
@@ -0,0 +1,58 @@
+use async_recursion::async_recursion;
+struct Cursor<'a> {
+    input: &'a [u8],
+}
+trait Visit {}
+struct S;
+impl S {
+    #[must_use]
+    fn advance<
+        '__async_recursion_life0,
+        '__async_recursion_life1,
+        '__async_recursion_life_self,
+        'async_recursion,
+    >(
+        &'__async_recursion_life_self self,
+        cursor: Cursor<'__async_recursion_life0>,
+        visit: Box<dyn Visit + Send + '__async_recursion_life1>,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<
+                Output = usize,
+            > + 'async_recursion + ::core::marker::Send,
+        >,
+    >
+    where
+        '__async_recursion_life0: 'async_recursion,
+        '__async_recursion_life1: 'async_recursion,
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion,
+    {
+        ::async_recursion::__private::Box::pin(async move {
+            match cursor.input.split_first() {
+                Some((_, rest)) => 1 + self.advance(Cursor { input: rest }, visit).await,
+                None => 0,
+            }
+        })
+    }
+}
+#[must_use]
+fn skip<'__async_recursion_life0, '__async_recursion_life1, 'async_recursion>(
+    cursor: &'__async_recursion_life0 mut Cursor<'__async_recursion_life1>,
+    n: usize,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = ()> + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if n > 0 {
+            cursor.input = &cursor.input[1..];
+            skip(cursor, n - 1).await
+        }
+    })
+}
@@ -0,0 +1,27 @@
+use async_recursion::async_recursion;
+
+struct Cursor<'a> {
+    input: &'a [u8],
+}
+
+trait Visit {}
+
+struct S;
+
+impl S {
+    #[async_recursion]
+    async fn advance(&'_ self, cursor: Cursor<'_>, visit: Box<dyn Visit + Send + '_>) -> usize {
+        match cursor.input.split_first() {
+            Some((_, rest)) => 1 + self.advance(Cursor { input: rest }, visit).await,
+            None => 0,
+        }
+    }
+}
+
+#[async_recursion]
+async fn skip(cursor: &'_ mut Cursor<'_>, n: usize) {
+    if n > 0 {
+        cursor.input = &cursor.input[1..];
+        skip(cursor, n - 1).await
+    }
+}
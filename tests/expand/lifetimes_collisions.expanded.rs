@@ -0,0 +1,55 @@
+use async_recursion::async_recursion;
+struct Tree<'life0> {
+    value: &'life0 u64,
+    children: Vec<Tree<'life0>>,
+}
+impl<'life0> Tree<'life0> {
+    #[must_use]
+    fn sum<'__async_recursion_life0, '__async_recursion_life_self, 'async_recursion>(
+        &'__async_recursion_life_self self,
+        scale: &'__async_recursion_life0 u64,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<
+                Output = u64,
+            > + 'async_recursion + ::core::marker::Send,
+        >,
+    >
+    where
+        '__async_recursion_life0: 'async_recursion,
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion,
+    {
+        ::async_recursion::__private::Box::pin(async move {
+            let mut total = self.value * scale;
+            for child in &self.children {
+                total += child.sum(scale).await;
+            }
+            total
+        })
+    }
+}
+#[must_use]
+fn count<'life_self, 'async_recursion, '__async_recursion_life0, 'async_recursion0>(
+    tree: &'life_self Tree<'async_recursion>,
+    extra: &'__async_recursion_life0 u64,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = u64,
+        > + 'async_recursion0 + ::core::marker::Send,
+    >,
+>
+where
+    'life_self: 'async_recursion0,
+    '__async_recursion_life0: 'async_recursion0,
+    'async_recursion: 'async_recursion0,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        let mut total = 1;
+        for child in &tree.children {
+            total += count(child, extra).await;
+        }
+        total + extra
+    })
+}
@@ -0,0 +1,29 @@
+use async_recursion::async_recursion;
+
+struct Tree<'life0> {
+    value: &'life0 u64,
+    children: Vec<Tree<'life0>>,
+}
+
+#[async_recursion]
+impl<'life0> Tree<'life0> {
+    async fn sum(&self, scale: &u64) -> u64 {
+        let mut total = self.value * scale;
+        for child in &self.children {
+            total += child.sum(scale).await;
+        }
+        total
+    }
+}
+
+#[async_recursion]
+async fn count<'life_self, 'async_recursion>(
+    tree: &'life_self Tree<'async_recursion>,
+    extra: &u64,
+) -> u64 {
+    let mut total = 1;
+    for child in &tree.children {
+        total += count(child, extra).await;
+    }
+    total + extra
+}
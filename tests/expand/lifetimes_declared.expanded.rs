@@ -0,0 +1,44 @@
+use async_recursion::async_recursion;
+struct Node<'a, T> {
+    value: &'a T,
+}
+trait Parse<'de> {}
+#[must_use]
+fn depth<'a, 'async_recursion, T: Sync>(
+    node: Node<'a, T>,
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = u32,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    T: 'async_recursion,
+    'a: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if n == 0 { 0 } else { depth(node, n - 1).await }
+    })
+}
+#[must_use]
+fn parse<'de, 'async_recursion, P>(
+    parser: P,
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = u32,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    P: Parse<'de> + Send,
+    P: 'async_recursion,
+    'de: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if n == 0 { 0 } else { parse(parser, n - 1).await }
+    })
+}
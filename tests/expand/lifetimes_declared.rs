@@ -0,0 +1,20 @@
+use async_recursion::async_recursion;
+
+struct Node<'a, T> {
+    value: &'a T,
+}
+
+trait Parse<'de> {}
+
+#[async_recursion]
+async fn depth<'a, T: Sync>(node: Node<'a, T>, n: u32) -> u32 {
+    if n == 0 { 0 } else { depth(node, n - 1).await }
+}
+
+#[async_recursion]
+async fn parse<'de, P>(parser: P, n: u32) -> u32
+where
+    P: Parse<'de> + Send,
+{
+    if n == 0 { 0 } else { parse(parser, n - 1).await }
+}
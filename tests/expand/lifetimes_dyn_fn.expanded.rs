@@ -0,0 +1,21 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn apply<'__async_recursion_life0, '__async_recursion_life1, 'async_recursion>(
+    f: &'__async_recursion_life0 (dyn Fn(&str) -> &str + Sync),
+    input: &'__async_recursion_life1 str,
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = usize,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if n == 0 { f(input).len() } else { apply(f, input, n - 1).await }
+    })
+}
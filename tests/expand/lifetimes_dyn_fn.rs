@@ -0,0 +1,6 @@
+use async_recursion::async_recursion;
+
+#[async_recursion]
+async fn apply(f: &(dyn Fn(&str) -> &str + Sync), input: &str, n: u32) -> usize {
+    if n == 0 { f(input).len() } else { apply(f, input, n - 1).await }
+}
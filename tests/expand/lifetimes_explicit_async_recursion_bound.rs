@@ -3,7 +3,7 @@
 use async_recursion::async_recursion;
 
 
-#[async_recursion]
+#[async_recursion(allow_nonrecursive)]
 async fn explicit_async_recursion_bound(
     t: &T,
     p: &[String],
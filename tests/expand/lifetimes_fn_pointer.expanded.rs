@@ -0,0 +1,20 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn apply<'__async_recursion_life0, 'async_recursion>(
+    f: fn(&str) -> &str,
+    input: &'__async_recursion_life0 str,
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = usize,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    '__async_recursion_life0: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if n == 0 { f(input).len() } else { apply(f, input, n - 1).await }
+    })
+}
@@ -0,0 +1,43 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn apply<'__async_recursion_life0, '__async_recursion_life1, 'async_recursion, F>(
+    f: &'__async_recursion_life0 F,
+    input: &'__async_recursion_life1 str,
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = usize,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    F: for<'a> Fn(&'a str) -> &'a str + Sync,
+    F: 'async_recursion,
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if n == 0 { f(input).len() } else { apply(f, input, n - 1).await }
+    })
+}
+#[must_use]
+fn apply_dyn<'__async_recursion_life0, '__async_recursion_life1, 'async_recursion>(
+    f: &'__async_recursion_life0 (dyn for<'a> Fn(&'a str) -> &'a str + Sync),
+    input: &'__async_recursion_life1 str,
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = usize,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if n == 0 { f(input).len() } else { apply_dyn(f, input, n - 1).await }
+    })
+}
@@ -0,0 +1,14 @@
+use async_recursion::async_recursion;
+
+#[async_recursion]
+async fn apply<F>(f: &F, input: &str, n: u32) -> usize
+where
+    F: for<'a> Fn(&'a str) -> &'a str + Sync,
+{
+    if n == 0 { f(input).len() } else { apply(f, input, n - 1).await }
+}
+
+#[async_recursion]
+async fn apply_dyn(f: &(dyn for<'a> Fn(&'a str) -> &'a str + Sync), input: &str, n: u32) -> usize {
+    if n == 0 { f(input).len() } else { apply_dyn(f, input, n - 1).await }
+}
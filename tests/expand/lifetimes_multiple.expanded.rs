@@ -3,10 +3,17 @@ use async_recursion::async_recursion;
 fn contains_value_2<'a, 'b, 'async_recursion, T: PartialEq>(
     value: &'b T,
     node: &'b Node<'a, T>,
-) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = bool> + 'async_recursion>>
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = bool> + 'async_recursion,
+    >,
+>
 where
     T: 'async_recursion,
     'b: 'async_recursion,
+    'a: 'async_recursion,
 {
-    Box::pin(async move { contains_value(value, node).await })
+    ::async_recursion::__private::Box::pin(async move {
+        contains_value(value, node).await
+    })
 }
@@ -1,18 +1,18 @@
 use async_recursion::async_recursion;
 #[must_use]
-fn count_down<'life0, 'async_recursion>(
-    foo: Option<&'life0 str>,
+fn count_down<'__async_recursion_life0, 'async_recursion>(
+    foo: Option<&'__async_recursion_life0 str>,
 ) -> ::core::pin::Pin<
-    Box<
+    ::async_recursion::__private::Box<
         dyn ::core::future::Future<
             Output = i32,
         > + 'async_recursion + ::core::marker::Send,
     >,
 >
 where
-    'life0: 'async_recursion,
+    '__async_recursion_life0: 'async_recursion,
 {
-    Box::pin(async move {
+    ::async_recursion::__private::Box::pin(async move {
         let _ = foo;
         0
     })
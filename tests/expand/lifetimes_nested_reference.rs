@@ -1,6 +1,6 @@
 use async_recursion::async_recursion;
 
-#[async_recursion]
+#[async_recursion(allow_nonrecursive)]
 async fn count_down(foo: Option<&str>) -> i32 {
     let _ = foo;
     0
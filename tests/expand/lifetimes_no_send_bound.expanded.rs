@@ -3,14 +3,27 @@ struct Node<'a, T> {
     ptr: &'a T,
 }
 #[must_use]
-fn contains_value<'a, 'life0, 'life1, 'async_recursion, T: PartialEq>(
-    value: &'life0 T,
-    node: &'life1 Node<'a, T>,
-) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = bool> + 'async_recursion>>
+fn contains_value<
+    'a,
+    '__async_recursion_life0,
+    '__async_recursion_life1,
+    'async_recursion,
+    T: PartialEq,
+>(
+    value: &'__async_recursion_life0 T,
+    node: &'__async_recursion_life1 Node<'a, T>,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = bool> + 'async_recursion,
+    >,
+>
 where
     T: 'async_recursion,
-    'life0: 'async_recursion,
-    'life1: 'async_recursion,
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
+    'a: 'async_recursion,
 {
-    Box::pin(async move { if &node.ptr == value { true } else { false } })
+    ::async_recursion::__private::Box::pin(async move {
+        if &node.ptr == value { true } else { false }
+    })
 }
@@ -4,7 +4,7 @@ struct Node<'a, T> {
     ptr: &'a T,
 }
 
-#[async_recursion(?Send)]
+#[async_recursion(?Send, allow_nonrecursive)]
 async fn contains_value<'a, T: PartialEq>(value: &T, node: &Node<'a, T>) -> bool {
     if &node.ptr == value {
         true
@@ -0,0 +1,47 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn sum<
+    '__async_recursion_life0,
+    '__async_recursion_life1,
+    'async_recursion,
+    T: Copy + Into<u64> + Sync,
+>(
+    values: &'__async_recursion_life0 Vec<&'__async_recursion_life1 T>,
+    n: usize,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = u64,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    T: 'async_recursion,
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        if n == 0 { 0 } else { values[n - 1].clone().into() + sum(values, n - 1).await }
+    })
+}
+#[must_use]
+fn first<'a, '__async_recursion_life0, 'async_recursion>(
+    values: &'a [&'__async_recursion_life0 Option<&'a str>],
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<
+            Output = Option<&'a str>,
+        > + 'async_recursion + ::core::marker::Send,
+    >,
+>
+where
+    'a: 'async_recursion,
+    '__async_recursion_life0: 'async_recursion,
+{
+    ::async_recursion::__private::Box::pin(async move {
+        match values.split_first() {
+            Some((head, rest)) => head.or(first(rest).await),
+            None => None,
+        }
+    })
+}
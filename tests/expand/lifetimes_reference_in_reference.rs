@@ -0,0 +1,14 @@
+use async_recursion::async_recursion;
+
+#[async_recursion]
+async fn sum<T: Copy + Into<u64> + Sync>(values: &Vec<&T>, n: usize) -> u64 {
+    if n == 0 { 0 } else { values[n - 1].clone().into() + sum(values, n - 1).await }
+}
+
+#[async_recursion]
+async fn first<'a>(values: &'a [&Option<&'a str>]) -> Option<&'a str> {
+    match values.split_first() {
+        Some((head, rest)) => head.or(first(rest).await),
+        None => None,
+    }
+}
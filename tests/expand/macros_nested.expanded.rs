@@ -1,56 +1,56 @@
 #[must_use]
-fn owned<'life0, 'async_recursion, F>(
+fn owned<'__async_recursion_life0, 'async_recursion, F>(
     param: usize,
-    f: &'life0 F,
+    f: &'__async_recursion_life0 F,
 ) -> ::core::pin::Pin<
-    Box<
+    ::async_recursion::__private::Box<
         dyn ::core::future::Future<Output = ()> + 'async_recursion + ::core::marker::Send,
     >,
 >
 where
     F: Fn(usize) + Sync + Send,
     F: 'async_recursion,
-    'life0: 'async_recursion,
+    '__async_recursion_life0: 'async_recursion,
 {
-    Box::pin(async move {
+    ::async_recursion::__private::Box::pin(async move {
         f(param);
     })
 }
 #[must_use]
-fn by_ref<'life0, 'life1, 'async_recursion, F>(
-    param: &'life0 usize,
-    f: &'life1 F,
+fn by_ref<'__async_recursion_life0, '__async_recursion_life1, 'async_recursion, F>(
+    param: &'__async_recursion_life0 usize,
+    f: &'__async_recursion_life1 F,
 ) -> ::core::pin::Pin<
-    Box<
+    ::async_recursion::__private::Box<
         dyn ::core::future::Future<Output = ()> + 'async_recursion + ::core::marker::Send,
     >,
 >
 where
     F: Fn(&usize) + Sync + Send,
     F: 'async_recursion,
-    'life0: 'async_recursion,
-    'life1: 'async_recursion,
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
 {
-    Box::pin(async move {
+    ::async_recursion::__private::Box::pin(async move {
         f(param);
     })
 }
 #[must_use]
-fn by_ref_mut<'life0, 'life1, 'async_recursion, F>(
-    param: &'life0 mut usize,
-    f: &'life1 F,
+fn by_ref_mut<'__async_recursion_life0, '__async_recursion_life1, 'async_recursion, F>(
+    param: &'__async_recursion_life0 mut usize,
+    f: &'__async_recursion_life1 F,
 ) -> ::core::pin::Pin<
-    Box<
+    ::async_recursion::__private::Box<
         dyn ::core::future::Future<Output = ()> + 'async_recursion + ::core::marker::Send,
     >,
 >
 where
     F: Fn(&mut usize) + Sync + Send,
     F: 'async_recursion,
-    'life0: 'async_recursion,
-    'life1: 'async_recursion,
+    '__async_recursion_life0: 'async_recursion,
+    '__async_recursion_life1: 'async_recursion,
 {
-    Box::pin(async move {
+    ::async_recursion::__private::Box::pin(async move {
         f(param);
     })
 }
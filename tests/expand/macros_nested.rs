@@ -1,6 +1,6 @@
 macro_rules! recurse {
     ($name:ident, $param:ty) => {
-        #[::async_recursion::async_recursion]
+        #[::async_recursion::async_recursion(allow_nonrecursive)]
         async fn $name<F>(param: $param, f: &F)
         where
             F: Fn($param) + Sync + Send,
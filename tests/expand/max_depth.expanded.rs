@@ -0,0 +1,64 @@
+use async_recursion::{async_recursion, DepthExceeded};
+struct Error;
+impl From<DepthExceeded> for Error {
+    fn from(_: DepthExceeded) -> Self {
+        Error
+    }
+}
+#[must_use]
+fn countdown(
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = u32> + ::core::marker::Send,
+    >,
+> {
+    static __ASYNC_RECURSION_KEY: u8 = 0;
+    let __async_recursion_depth = ::async_recursion::__private::depth::child_depth(
+        &__ASYNC_RECURSION_KEY,
+    );
+    ::async_recursion::__private::Box::pin(
+        ::async_recursion::__private::depth::WithDepth::new(
+            &__ASYNC_RECURSION_KEY,
+            __async_recursion_depth,
+            async move {
+                if __async_recursion_depth > 100usize {
+                    ::async_recursion::__private::depth::panic_exceeded(
+                        "countdown",
+                        100usize,
+                    );
+                }
+                if n == 0 { 0 } else { countdown(n - 1).await }
+            },
+        ),
+    )
+}
+#[must_use]
+fn try_countdown(
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = Result<u32, Error>> + ::core::marker::Send,
+    >,
+> {
+    static __ASYNC_RECURSION_KEY: u8 = 0;
+    let __async_recursion_depth = ::async_recursion::__private::depth::child_depth(
+        &__ASYNC_RECURSION_KEY,
+    );
+    ::async_recursion::__private::Box::pin(
+        ::async_recursion::__private::depth::WithDepth::new(
+            &__ASYNC_RECURSION_KEY,
+            __async_recursion_depth,
+            async move {
+                if __async_recursion_depth > 100usize {
+                    return ::core::result::Result::Err(
+                        ::core::convert::From::from(
+                            ::async_recursion::__private::depth::exceeded(100usize),
+                        ),
+                    );
+                }
+                if n == 0 { Ok(0) } else { try_countdown(n - 1).await }
+            },
+        ),
+    )
+}
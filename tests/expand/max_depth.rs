@@ -0,0 +1,19 @@
+use async_recursion::{async_recursion, DepthExceeded};
+
+struct Error;
+
+impl From<DepthExceeded> for Error {
+    fn from(_: DepthExceeded) -> Self {
+        Error
+    }
+}
+
+#[async_recursion(max_depth = 100)]
+async fn countdown(n: u32) -> u32 {
+    if n == 0 { 0 } else { countdown(n - 1).await }
+}
+
+#[async_recursion(max_depth = 100)]
+async fn try_countdown(n: u32) -> Result<u32, Error> {
+    if n == 0 { Ok(0) } else { try_countdown(n - 1).await }
+}
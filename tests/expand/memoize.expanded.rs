@@ -0,0 +1,43 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn fib(
+    n: u32,
+) -> ::async_recursion::Memoized<
+    dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+> {
+    static __ASYNC_RECURSION_MEMO: u8 = 0;
+    ::async_recursion::__private::memoize::call(
+        &__ASYNC_RECURSION_MEMO,
+        (::core::clone::Clone::clone(&n),),
+        move || async move {
+            match n {
+                0 | 1 => n as u64,
+                _ => fib(n - 1).await + fib(n - 2).await,
+            }
+        },
+    )
+}
+#[must_use]
+fn count_paths(
+    width: u32,
+    height: u32,
+) -> ::async_recursion::Memoized<
+    dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+> {
+    static __ASYNC_RECURSION_MEMO: ::async_recursion::__private::memoize::Memo<
+        (u32, u32),
+        dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+    > = ::async_recursion::__private::memoize::Memo::new();
+    __ASYNC_RECURSION_MEMO
+        .get(
+            (::core::clone::Clone::clone(&width), ::core::clone::Clone::clone(&height)),
+            move || async move {
+                if width == 0 || height == 0 {
+                    1
+                } else {
+                    count_paths(width - 1, height).await
+                        + count_paths(width, height - 1).await
+                }
+            },
+        )
+}
@@ -0,0 +1,18 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(memoize)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => n as u64,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[async_recursion(memoize(global))]
+async fn count_paths(width: u32, height: u32) -> u64 {
+    if width == 0 || height == 0 {
+        1
+    } else {
+        count_paths(width - 1, height).await + count_paths(width, height - 1).await
+    }
+}
@@ -0,0 +1,61 @@
+use async_recursion::async_recursion;
+mod cycles {
+    #[must_use]
+    fn a(
+        n: u32,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<Output = u32> + ::core::marker::Send,
+        >,
+    > {
+        ::async_recursion::__private::Box::pin(async move {
+            if n == 0 { 0 } else { b(n - 1).await }
+        })
+    }
+    async fn b(n: u32) -> u32 {
+        c(n).await
+    }
+    async fn c(n: u32) -> u32 {
+        a(n).await + d(n).await
+    }
+    async fn d(n: u32) -> u32 {
+        n
+    }
+    #[must_use]
+    fn e(
+        n: u32,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<Output = u32> + ::core::marker::Send,
+        >,
+    > {
+        ::async_recursion::__private::Box::pin(async move {
+            if n == 0 { 0 } else { self::e(n - 1).await }
+        })
+    }
+    #[must_use]
+    fn f(
+        n: u32,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<dyn ::core::future::Future<Output = u32>>,
+    > {
+        ::async_recursion::__private::Box::pin(async move { d(n).await })
+    }
+    async fn g(n: u32) -> u32 {
+        h(n).await
+    }
+    #[must_use]
+    fn h(
+        n: u32,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<Output = u32> + ::core::marker::Send,
+        >,
+    > {
+        ::async_recursion::__private::Box::pin(async move { g(n).await + i(n).await })
+    }
+    async fn i(n: u32) -> u32 {
+        h(n).await
+    }
+    fn not_async() {}
+}
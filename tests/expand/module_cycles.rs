@@ -0,0 +1,43 @@
+use async_recursion::async_recursion;
+
+#[async_recursion]
+mod cycles {
+    async fn a(n: u32) -> u32 {
+        if n == 0 { 0 } else { b(n - 1).await }
+    }
+
+    async fn b(n: u32) -> u32 {
+        c(n).await
+    }
+
+    async fn c(n: u32) -> u32 {
+        a(n).await + d(n).await
+    }
+
+    async fn d(n: u32) -> u32 {
+        n
+    }
+
+    async fn e(n: u32) -> u32 {
+        if n == 0 { 0 } else { self::e(n - 1).await }
+    }
+
+    #[async_recursion(?Send, allow_nonrecursive)]
+    async fn f(n: u32) -> u32 {
+        d(n).await
+    }
+
+    async fn g(n: u32) -> u32 {
+        h(n).await
+    }
+
+    async fn h(n: u32) -> u32 {
+        g(n).await + i(n).await
+    }
+
+    async fn i(n: u32) -> u32 {
+        h(n).await
+    }
+
+    fn not_async() {}
+}
@@ -0,0 +1,49 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn fib(
+    n: u32,
+) -> ::async_recursion::Pooled<
+    dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+> {
+    const __ASYNC_RECURSION_POOL: ::std::thread::LocalKey<
+        ::async_recursion::__private::pool::Pool,
+    > = {
+        const __RUST_STD_INTERNAL_INIT: ::async_recursion::__private::pool::Pool = {
+            ::async_recursion::__private::pool::Pool::new()
+        };
+        unsafe {
+            ::std::thread::LocalKey::new(const {
+                if ::std::mem::needs_drop::<::async_recursion::__private::pool::Pool>() {
+                    |_| {
+                        #[thread_local]
+                        static __RUST_STD_INTERNAL_VAL: ::std::thread::local_impl::EagerStorage<
+                            ::async_recursion::__private::pool::Pool,
+                        > = ::std::thread::local_impl::EagerStorage::new(
+                            __RUST_STD_INTERNAL_INIT,
+                        );
+                        __RUST_STD_INTERNAL_VAL.get()
+                    }
+                } else {
+                    |_| {
+                        #[thread_local]
+                        static __RUST_STD_INTERNAL_VAL: ::async_recursion::__private::pool::Pool = __RUST_STD_INTERNAL_INIT;
+                        &__RUST_STD_INTERNAL_VAL
+                    }
+                }
+            })
+        }
+    };
+    ::async_recursion::Pooled::new(
+        &__ASYNC_RECURSION_POOL,
+        async move {
+            match n {
+                0 => {
+                    ::std::rt::begin_panic("zero is not a valid argument to fib()!");
+                }
+                1 | 2 => 1,
+                3 => 2,
+                _ => fib(n - 1).await + fib(n - 2).await,
+            }
+        },
+    )
+}
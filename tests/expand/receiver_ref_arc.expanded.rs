@@ -0,0 +1,27 @@
+use std::{pin::Pin, sync::Arc};
+use async_recursion::async_recursion;
+struct S;
+impl S {
+    #[must_use]
+    fn receiver<
+        '__async_recursion_life0,
+        '__async_recursion_life_self,
+        'async_recursion,
+    >(
+        self: &'__async_recursion_life_self Arc<Self>,
+        x: &'__async_recursion_life0 u64,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<
+                Output = u64,
+            > + 'async_recursion + ::core::marker::Send,
+        >,
+    >
+    where
+        '__async_recursion_life0: 'async_recursion,
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion,
+    {
+        ::async_recursion::__private::Box::pin(async move { *x })
+    }
+}
@@ -0,0 +1,12 @@
+use std::{pin::Pin, sync::Arc};
+
+use async_recursion::async_recursion;
+
+struct S;
+
+impl S {
+    #[async_recursion(allow_nonrecursive)]
+    async fn receiver(self: &Arc<Self>, x: &u64) -> u64 {
+        *x
+    }
+}
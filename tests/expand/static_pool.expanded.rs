@@ -0,0 +1,32 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn fib(
+    n: u32,
+) -> ::async_recursion::StaticBox<
+    dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+> {
+    async fn __async_recursion_future((n,): (u32,)) -> u64 {
+        match n {
+            0 => {
+                ::std::rt::begin_panic("zero is not a valid argument to fib()!");
+            }
+            1 | 2 => 1,
+            3 => 2,
+            _ => fib(n - 1).await + fib(n - 2).await,
+        }
+    }
+    static __ASYNC_RECURSION_SLOTS: ::async_recursion::__private::static_pool::StaticPool<
+        16usize,
+        {
+            ::async_recursion::__private::static_pool::chunks_for_output(
+                &__async_recursion_future,
+            )
+        },
+    > = ::async_recursion::__private::static_pool::StaticPool::new();
+    match __ASYNC_RECURSION_SLOTS.alloc(__async_recursion_future((n,))) {
+        ::core::result::Result::Ok(future) => future,
+        ::core::result::Result::Err(_) => {
+            ::async_recursion::__private::static_pool::panic_exhausted("fib", 16usize)
+        }
+    }
+}
@@ -3,7 +3,7 @@ use async_recursion::async_recursion;
 struct S;
 
 impl S {
-    #[async_recursion]
+    #[async_recursion(allow_nonrecursive)]
     pub async fn all_of_the_above<'a, 'b, S, T>(
         &self,
         // Some references with / without lifetimes to generic parameters
@@ -0,0 +1,27 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn sum(
+    n: u64,
+    acc: u64,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+    >,
+> {
+    ::async_recursion::__private::Box::pin(async move {
+        #[allow(unused_mut)]
+        let mut __async_recursion_args = (n, acc);
+        '__async_recursion_tail: loop {
+            let (mut n, acc) = __async_recursion_args;
+            if n == 0 {
+                return acc;
+            }
+            n -= 1;
+            #[allow(unreachable_code)]
+            break '__async_recursion_tail {
+                __async_recursion_args = (n, acc + n + 1);
+                continue '__async_recursion_tail;
+            };
+        }
+    })
+}
@@ -0,0 +1,10 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(tail)]
+async fn sum(mut n: u64, acc: u64) -> u64 {
+    if n == 0 {
+        return acc;
+    }
+    n -= 1;
+    sum(n, acc + n + 1).await
+}
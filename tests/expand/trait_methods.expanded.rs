@@ -0,0 +1,79 @@
+use async_recursion::async_recursion;
+trait Visitor {
+    type Node: Sync;
+    fn is_leaf(&self, node: &Self::Node) -> bool;
+    #[must_use]
+    fn visit<'__async_recursion_life0, '__async_recursion_life_self, 'async_recursion>(
+        &'__async_recursion_life_self self,
+        node: &'__async_recursion_life0 Self::Node,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<
+                Output = u64,
+            > + 'async_recursion + ::core::marker::Send,
+        >,
+    >
+    where
+        '__async_recursion_life0: 'async_recursion,
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion;
+    #[must_use]
+    fn visit_mut<
+        '__async_recursion_life0,
+        '__async_recursion_life_self,
+        'async_recursion,
+    >(
+        &'__async_recursion_life_self mut self,
+        node: &'__async_recursion_life0 Self::Node,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<
+                Output = u64,
+            > + 'async_recursion + ::core::marker::Send,
+        >,
+    >
+    where
+        '__async_recursion_life0: 'async_recursion,
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion,
+        Self: ::core::marker::Send,
+    {
+        ::async_recursion::__private::Box::pin(async move { self.visit(node).await })
+    }
+    #[must_use]
+    fn visit_local<
+        '__async_recursion_life0,
+        '__async_recursion_life_self,
+        'async_recursion,
+    >(
+        &'__async_recursion_life_self self,
+        node: &'__async_recursion_life0 Self::Node,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<Output = u64> + 'async_recursion,
+        >,
+    >
+    where
+        '__async_recursion_life0: 'async_recursion,
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion,
+    {
+        ::async_recursion::__private::Box::pin(async move { self.visit(node).await })
+    }
+}
+trait Countdown {
+    #[must_use]
+    fn countdown<'__async_recursion_life_self, 'async_recursion>(
+        &'__async_recursion_life_self self,
+        n: u32,
+    ) -> ::core::pin::Pin<
+        ::async_recursion::__private::Box<
+            dyn ::core::future::Future<
+                Output = u32,
+            > + 'async_recursion + ::core::marker::Send,
+        >,
+    >
+    where
+        '__async_recursion_life_self: 'async_recursion,
+        Self: 'async_recursion;
+}
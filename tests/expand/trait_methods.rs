@@ -0,0 +1,24 @@
+use async_recursion::async_recursion;
+
+#[async_recursion]
+trait Visitor {
+    type Node: Sync;
+
+    fn is_leaf(&self, node: &Self::Node) -> bool;
+
+    async fn visit(&self, node: &Self::Node) -> u64;
+
+    async fn visit_mut(&mut self, node: &Self::Node) -> u64 {
+        self.visit(node).await
+    }
+
+    #[async_recursion(?Send)]
+    async fn visit_local(&self, node: &Self::Node) -> u64 {
+        self.visit(node).await
+    }
+}
+
+trait Countdown {
+    #[async_recursion]
+    async fn countdown(&self, n: u32) -> u32;
+}
@@ -0,0 +1,34 @@
+use async_recursion::async_recursion;
+#[must_use]
+fn fib(
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<
+        dyn ::core::future::Future<Output = u64> + ::core::marker::Send,
+    >,
+> {
+    ::async_recursion::__private::Box::pin(
+        ::async_recursion::__private::trampoline::Trampoline::new(async move {
+            match n {
+                0 => {
+                    ::std::rt::begin_panic("zero is not a valid argument to fib()!");
+                }
+                1 | 2 => 1,
+                3 => 2,
+                _ => fib(n - 1).await + fib(n - 2).await,
+            }
+        }),
+    )
+}
+#[must_use]
+fn countdown(
+    n: u32,
+) -> ::core::pin::Pin<
+    ::async_recursion::__private::Box<dyn ::core::future::Future<Output = u32>>,
+> {
+    ::async_recursion::__private::Box::pin(
+        ::async_recursion::__private::trampoline::Trampoline::new(async move {
+            if n == 0 { 0 } else { countdown(n - 1).await }
+        }),
+    )
+}
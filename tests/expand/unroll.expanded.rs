@@ -0,0 +1,41 @@
+use async_recursion::async_recursion;
+async fn countdown(n: u32) -> u32 {
+    async fn __async_recursion_level_1(n: u32) -> u32 {
+        if n == 0 { 0 } else { 1 + __async_recursion_level_2(n - 1).await }
+    }
+    async fn __async_recursion_level_2(n: u32) -> u32 {
+        if n == 0 {
+            0
+        } else {
+            1 + ::async_recursion::__private::Box::pin(countdown(n - 1)).await
+        }
+    }
+    if n == 0 { 0 } else { 1 + __async_recursion_level_1(n - 1).await }
+}
+async fn try_countdown(n: u32) -> Result<u32, async_recursion::DepthExceeded> {
+    async fn __async_recursion_level_1(
+        n: u32,
+    ) -> Result<u32, async_recursion::DepthExceeded> {
+        if n == 0 {
+            Ok(0)
+        } else {
+            Ok(
+                1
+                    + {
+                        let _ = (n - 1,);
+                        ::core::future::ready::<
+                            Result<u32, async_recursion::DepthExceeded>,
+                        >(
+                            ::core::result::Result::Err(
+                                ::core::convert::From::from(
+                                    ::async_recursion::__private::depth::exceeded(2usize),
+                                ),
+                            ),
+                        )
+                    }
+                        .await?,
+            )
+        }
+    }
+    if n == 0 { Ok(0) } else { Ok(1 + __async_recursion_level_1(n - 1).await?) }
+}
@@ -0,0 +1,11 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(unroll = 3)]
+async fn countdown(n: u32) -> u32 {
+    if n == 0 { 0 } else { 1 + countdown(n - 1).await }
+}
+
+#[async_recursion(unroll = 2, no_alloc)]
+async fn try_countdown(n: u32) -> Result<u32, async_recursion::DepthExceeded> {
+    if n == 0 { Ok(0) } else { Ok(1 + try_countdown(n - 1).await?) }
+}
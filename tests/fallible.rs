@@ -0,0 +1,106 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::Arc,
+};
+
+use async_recursion::{async_recursion, AllocError};
+use futures_executor::block_on;
+
+const LARGE: usize = 4096;
+
+/// Fails large allocations once the current thread has run out of them.
+struct Limited;
+
+thread_local! {
+    static LARGE_ALLOCATIONS: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+unsafe impl GlobalAlloc for Limited {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() >= LARGE {
+            let left = LARGE_ALLOCATIONS.with(Cell::get);
+            if left == 0 {
+                return std::ptr::null_mut();
+            }
+            LARGE_ALLOCATIONS.with(|allocations| allocations.set(left - 1));
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Limited = Limited;
+
+fn with_large_allocations<T>(allocations: usize, f: impl FnOnce() -> T) -> T {
+    LARGE_ALLOCATIONS.with(|left| left.set(allocations));
+    let out = f();
+    LARGE_ALLOCATIONS.with(|left| left.set(usize::MAX));
+    out
+}
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    OutOfMemory,
+}
+
+impl From<AllocError> for Error {
+    fn from(_: AllocError) -> Self {
+        Error::OutOfMemory
+    }
+}
+
+/// Sums the numbers up to `n`, with a large enough future for every allocation to be limited.
+#[async_recursion(fallible)]
+async fn sum(n: u64, token: Arc<()>) -> Result<u64, Error> {
+    let scratch = [n; LARGE / 8];
+    if n == 0 {
+        Ok(0)
+    } else {
+        let rest = sum(n - 1, token.clone()).await?;
+        Ok(scratch[0] + rest)
+    }
+}
+
+#[async_recursion(Sync, fallible)]
+async fn sync_sum(n: u64) -> Result<u64, AllocError> {
+    if n == 0 {
+        Ok(0)
+    } else {
+        Ok(n + sync_sum(n - 1).await?)
+    }
+}
+
+#[test]
+fn fallible_recursion() {
+    let token = Arc::new(());
+    assert_eq!(block_on(sum(10, token.clone())), Ok(55));
+    assert_eq!(
+        with_large_allocations(11, || block_on(sum(10, token))),
+        Ok(55)
+    );
+}
+
+#[test]
+fn fallible_out_of_memory() {
+    let token = Arc::new(());
+    let out = with_large_allocations(5, || block_on(sum(10, token.clone())));
+    assert_eq!(out, Err(Error::OutOfMemory));
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    let out = with_large_allocations(0, || block_on(sum(10, token.clone())));
+    assert_eq!(out, Err(Error::OutOfMemory));
+    assert_eq!(Arc::strong_count(&token), 1);
+}
+
+fn assert_is_send_and_sync(_: impl Send + Sync) {}
+
+#[test]
+fn fallible_bounds() {
+    assert_is_send_and_sync(sync_sum(10));
+    assert_eq!(block_on(sync_sum(10)), Ok(55));
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "grow_stack")]
+
+mod common;
+
+use std::rc::Rc;
+
+use async_recursion::async_recursion;
+use common::with_small_stack;
+use futures_executor::block_on;
+
+#[async_recursion(grow_stack)]
+async fn sum(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        n + sum(n - 1).await
+    }
+}
+
+#[async_recursion(?Send, grow_stack)]
+async fn rc_sum(n: Rc<u64>) -> u64 {
+    if *n == 0 {
+        0
+    } else {
+        *n + rc_sum(Rc::new(*n - 1)).await
+    }
+}
+
+#[test]
+fn grow_stack_deep_recursion() {
+    let n = 100_000;
+    assert_eq!(with_small_stack(move || block_on(sum(n))), n * (n + 1) / 2);
+}
+
+fn assert_is_send(_: impl Send) {}
+
+#[test]
+fn grow_stack_bounds() {
+    assert_is_send(sum(10));
+    assert_eq!(
+        with_small_stack(|| block_on(rc_sum(Rc::new(10_000)))),
+        50_005_000
+    );
+}
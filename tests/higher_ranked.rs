@@ -0,0 +1,55 @@
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+fn trim(input: &str) -> &str {
+    input.trim()
+}
+
+#[async_recursion]
+async fn apply_fn_pointer(f: fn(&str) -> &str, input: &str, n: u32) -> usize {
+    if n == 0 {
+        f(input).len()
+    } else {
+        apply_fn_pointer(f, input, n - 1).await
+    }
+}
+
+#[async_recursion]
+async fn apply_dyn(f: &(dyn Fn(&str) -> &str + Sync), input: &str, n: u32) -> usize {
+    if n == 0 {
+        f(input).len()
+    } else {
+        apply_dyn(f, input, n - 1).await
+    }
+}
+
+#[async_recursion]
+async fn apply_higher_ranked<F>(f: &F, input: &str, n: u32) -> usize
+where
+    F: for<'a> Fn(&'a str) -> &'a str + Sync,
+{
+    if n == 0 {
+        f(input).len()
+    } else {
+        apply_higher_ranked(f, input, n - 1).await
+    }
+}
+
+#[async_recursion]
+async fn sum(values: &Vec<&u64>, n: usize) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        *values[n - 1] + sum(values, n - 1).await
+    }
+}
+
+#[test]
+fn higher_ranked_arguments_work() {
+    block_on(async move {
+        assert_eq!(apply_fn_pointer(trim, "  abc ", 3).await, 3);
+        assert_eq!(apply_dyn(&trim, "  abc ", 3).await, 3);
+        assert_eq!(apply_higher_ranked(&trim, "  abc ", 3).await, 3);
+        assert_eq!(sum(&vec![&1, &2, &3], 3).await, 6);
+    });
+}
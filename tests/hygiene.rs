@@ -0,0 +1,117 @@
+use futures_executor::block_on;
+
+mod shadowed_box {
+    use async_recursion::async_recursion;
+
+    #[allow(dead_code)]
+    struct Box;
+
+    #[async_recursion]
+    pub async fn fib(n: u32) -> u64 {
+        match n {
+            0 | 1 => 1,
+            _ => fib(n - 1).await + fib(n - 2).await,
+        }
+    }
+
+    #[async_recursion(call_site)]
+    pub async fn fib_call_site(n: u32) -> u64 {
+        match n {
+            0 | 1 => 1,
+            _ => fib_call_site(n - 1).await + fib_call_site(n - 2).await,
+        }
+    }
+}
+
+mod no_prelude {
+    #![no_implicit_prelude]
+
+    #[::async_recursion::async_recursion]
+    pub async fn fib(n: u32) -> u64 {
+        match n {
+            0 | 1 => 1,
+            _ => fib(n - 1).await + fib(n - 2).await,
+        }
+    }
+
+    #[::async_recursion::async_recursion(call_site)]
+    pub async fn fib_call_site(n: u32) -> u64 {
+        match n {
+            0 | 1 => 1,
+            _ => fib_call_site(n - 1).await + fib_call_site(n - 2).await,
+        }
+    }
+}
+
+mod colliding_lifetimes {
+    use async_recursion::async_recursion;
+
+    pub struct Tree<'life0> {
+        pub value: &'life0 u64,
+        pub children: Vec<Tree<'life0>>,
+    }
+
+    #[async_recursion]
+    impl<'life0> Tree<'life0> {
+        pub async fn sum(&self, scale: &u64) -> u64 {
+            let mut total = self.value * scale;
+            for child in &self.children {
+                total += child.sum(scale).await;
+            }
+            total
+        }
+    }
+
+    impl<'life0> Tree<'life0> {
+        #[async_recursion]
+        pub async fn max(&self, floor: &u64) -> u64 {
+            let mut max = *self.value.max(floor);
+            for child in &self.children {
+                max = max.max(child.max(floor).await);
+            }
+            max
+        }
+    }
+
+    #[async_recursion]
+    pub async fn count<'life_self, 'async_recursion>(
+        tree: &'life_self Tree<'async_recursion>,
+        extra: &u64,
+    ) -> u64 {
+        let mut total = 1;
+        for child in &tree.children {
+            total += count(child, extra).await;
+        }
+        total + extra
+    }
+}
+
+#[test]
+fn generated_paths_are_hygienic() {
+    block_on(async move {
+        assert_eq!(shadowed_box::fib(10).await, 89);
+        assert_eq!(shadowed_box::fib_call_site(10).await, 89);
+        assert_eq!(no_prelude::fib(10).await, 89);
+        assert_eq!(no_prelude::fib_call_site(10).await, 89);
+    });
+}
+
+#[test]
+fn generated_lifetimes_do_not_collide() {
+    use colliding_lifetimes::{count, Tree};
+
+    let (one, two) = (1, 2);
+    let tree = Tree {
+        value: &one,
+        children: vec![Tree {
+            value: &two,
+            children: vec![],
+        }],
+    };
+
+    block_on(async move {
+        assert_eq!(tree.sum(&3).await, 9);
+        assert_eq!(tree.max(&0).await, 2);
+        assert_eq!(count(&tree, &0).await, 2);
+    });
+}
@@ -0,0 +1,70 @@
+use std::rc::Rc;
+
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+struct Evaluator {
+    depth_limit: u32,
+}
+
+#[async_recursion]
+impl Evaluator {
+    const BASE: u64 = 1;
+
+    fn depth_limit(&self) -> u32 {
+        self.depth_limit
+    }
+
+    async fn is_even(&self, n: u32) -> bool {
+        match n {
+            0 => true,
+            _ => self.is_odd(n - 1).await,
+        }
+    }
+
+    async fn is_odd(&self, n: u32) -> bool {
+        match n {
+            0 => false,
+            _ => self.is_even(n - 1).await,
+        }
+    }
+
+    async fn sum_to(&self, n: u32) -> u64 {
+        if n == 0 || n > self.depth_limit() {
+            Self::BASE - 1
+        } else {
+            u64::from(n) + self.sum_to(n - 1).await
+        }
+    }
+
+    #[async_recursion(?Send)]
+    async fn count_rc(&self, counter: Rc<u32>) -> u32 {
+        if *counter == 0 {
+            0
+        } else {
+            let next = Rc::new(*counter - 1);
+            1 + self.count_rc(next).await
+        }
+    }
+}
+
+#[test]
+fn impl_block_methods_work() {
+    block_on(async move {
+        let e = Evaluator { depth_limit: 100 };
+        assert!(e.is_even(10).await);
+        assert!(e.is_odd(7).await);
+        assert!(!e.is_odd(8).await);
+        assert_eq!(e.sum_to(10).await, 55);
+        assert_eq!(e.count_rc(Rc::new(5)).await, 5);
+    });
+}
+
+#[test]
+fn impl_block_methods_are_send() {
+    fn assert_is_send(_: impl Send) {}
+
+    let e = Evaluator { depth_limit: 100 };
+    assert_is_send(e.is_even(4));
+    assert_is_send(e.sum_to(4));
+}
@@ -0,0 +1,59 @@
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+struct Node {
+    value: u32,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(value: u32, children: Vec<Node>) -> Self {
+        Self { value, children }
+    }
+}
+
+#[async_recursion]
+async fn walk(node: &Node, visit: &mut (impl FnMut(&Node) + Send)) {
+    visit(node);
+    for child in &node.children {
+        walk(child, visit).await;
+    }
+}
+
+#[async_recursion]
+async fn count(node: &Node, keep: impl Fn(&Node) -> bool + Copy + Send + Sync) -> usize {
+    let mut total = usize::from(keep(node));
+    for child in &node.children {
+        total += count(child, keep).await;
+    }
+    total
+}
+
+#[async_recursion]
+async fn sum_all(values: impl Iterator<Item = &u32> + Send, depth: u32) -> u32 {
+    if depth == 0 {
+        values.sum()
+    } else {
+        sum_all(values, depth - 1).await
+    }
+}
+
+#[test]
+fn impl_trait_arguments_work() {
+    let tree = Node::new(
+        1,
+        vec![
+            Node::new(2, vec![Node::new(3, vec![])]),
+            Node::new(4, vec![]),
+        ],
+    );
+
+    block_on(async move {
+        let mut seen = vec![];
+        walk(&tree, &mut |node: &Node| seen.push(node.value)).await;
+        assert_eq!(seen, vec![1, 2, 3, 4]);
+
+        assert_eq!(count(&tree, |node: &Node| node.value > 2).await, 2);
+        assert_eq!(sum_all(seen.iter(), 3).await, 10);
+    });
+}
@@ -0,0 +1,203 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    future::Future,
+    pin::{pin, Pin},
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+// Counts the allocations made on each thread, as the tests run side by side
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Creates a future and polls it to completion, returning its output and the number of
+// allocations made along the way.
+fn count_allocations<F: Future>(f: impl FnOnce() -> F) -> (F::Output, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let future = pin!(f());
+    let Poll::Ready(output) = future.poll(&mut Context::from_waker(Waker::noop())) else {
+        panic!("the future should complete without waiting");
+    };
+    (output, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[async_recursion(inline = 64)]
+async fn is_even(n: u64) -> bool {
+    if n == 0 {
+        true
+    } else {
+        is_odd(n - 1).await
+    }
+}
+
+#[async_recursion]
+async fn is_odd(n: u64) -> bool {
+    if n == 0 {
+        false
+    } else {
+        is_even(n - 1).await
+    }
+}
+
+#[async_recursion(inline = 64)]
+mod parity {
+    pub async fn is_even(n: u64) -> bool {
+        if n == 0 {
+            true
+        } else {
+            is_odd(n - 1).await
+        }
+    }
+
+    pub async fn is_odd(n: u64) -> bool {
+        if n == 0 {
+            false
+        } else {
+            is_even(n - 1).await
+        }
+    }
+}
+
+#[async_recursion(inline = 64)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => n as u64,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[async_recursion]
+async fn boxed_fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => n as u64,
+        _ => boxed_fib(n - 1).await + boxed_fib(n - 2).await,
+    }
+}
+
+#[async_recursion(?Send, inline = 64)]
+async fn rc_sum(n: Rc<u64>) -> u64 {
+    if *n == 0 {
+        0
+    } else {
+        *n + rc_sum(Rc::new(*n - 1)).await
+    }
+}
+
+#[async_recursion(Sync, inline = 64)]
+async fn sync_sum(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        n + sync_sum(n - 1).await
+    }
+}
+
+#[async_recursion(inline = 64)]
+async fn chunked_sum(n: u64) -> u64 {
+    // Held across the call, so the future can't fit in 64 bytes
+    let chunk = [n; 16];
+    if n == 0 {
+        0
+    } else {
+        chunked_sum(n - 1).await + chunk.iter().sum::<u64>()
+    }
+}
+
+#[async_recursion(inline = 64)]
+async fn hold(n: u32, token: Arc<()>) {
+    if n == 0 {
+        std::future::pending::<()>().await;
+    } else {
+        hold(n - 1, token.clone()).await;
+    }
+}
+
+#[test]
+fn inline_small_futures() {
+    let future = is_even(10);
+    assert!(future.is_inline());
+    assert!(block_on(future));
+    assert!(!block_on(is_even(7)));
+    assert!(block_on(is_odd(7)));
+}
+
+#[test]
+fn inline_recursive_calls() {
+    // The calls it makes to itself are boxed, which leaves the outermost call small enough
+    let future = fib(20);
+    assert!(future.is_inline());
+    assert_eq!(block_on(future), 6765);
+
+    // So are the calls made between the functions under the same attribute
+    let future = parity::is_even(10);
+    assert!(future.is_inline());
+    assert!(block_on(future));
+}
+
+#[test]
+fn inline_saves_only_the_outermost_allocation() {
+    // fib(10) makes 177 calls, more than half of which are leaves
+    assert_eq!(count_allocations(|| boxed_fib(10)), (55, 177));
+    assert_eq!(count_allocations(|| fib(10)), (55, 176));
+}
+
+#[test]
+fn inline_falls_back_to_box() {
+    let future = chunked_sum(10);
+    assert!(!future.is_inline());
+    assert_eq!(block_on(future), 16 * 55);
+}
+
+#[test]
+fn inline_converts_to_box() {
+    let future: Pin<Box<dyn Future<Output = bool> + Send>> = is_even(10).into();
+    assert!(block_on(future));
+    assert_eq!(block_on(sync_sum(10)), 55);
+    assert_eq!(block_on(rc_sum(Rc::new(10))), 55);
+}
+
+fn assert_is_send(_: impl Send) {}
+
+fn assert_is_send_and_sync(_: impl Send + Sync) {}
+
+#[test]
+fn inline_bounds() {
+    assert_is_send(is_even(10));
+    assert_is_send_and_sync(sync_sum(10));
+}
+
+#[test]
+fn inline_drops_pending_calls() {
+    let token = Arc::new(());
+    {
+        let mut future = pin!(hold(100, token.clone()));
+        assert!(future.is_inline());
+
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(Arc::strong_count(&token), 102);
+    }
+    assert_eq!(Arc::strong_count(&token), 1);
+}
@@ -29,19 +29,19 @@ async fn contains_value<'a, T: PartialEq>(value: &T, node: &Node<'a, T>) -> bool
     }
 }
 
-#[async_recursion(?Send)]
+#[async_recursion(?Send, allow_nonrecursive)]
 async fn contains_value_2<'a, 'b, T: PartialEq>(value: &'b T, node: &'b Node<'a, T>) -> bool {
     contains_value(value, node).await
 }
 
 // The reference inside foo needs a `async_recursion bound
-#[async_recursion]
+#[async_recursion(allow_nonrecursive)]
 async fn count_down(foo: Option<&str>) -> i32 {
     let _ = foo;
     0
 }
 
-#[async_recursion]
+#[async_recursion(allow_nonrecursive)]
 async fn explicit_async_recursion_bound(_: Option<&'async_recursion String>) {}
 
 #[test]
@@ -79,3 +79,36 @@ fn lifetime_expansion_works() {
         explicit_async_recursion_bound(None).await;
     });
 }
+
+struct Cursor<'a> {
+    input: &'a [u8],
+}
+
+#[async_recursion]
+async fn skip(cursor: &'_ mut Cursor<'_>, n: usize) {
+    if n > 0 {
+        cursor.input = &cursor.input[1..];
+        skip(cursor, n - 1).await
+    }
+}
+
+#[async_recursion]
+async fn remaining(cursor: Cursor<'_>, extra: Box<dyn Fn() -> usize + Send + Sync + '_>) -> usize {
+    match cursor.input.split_first() {
+        Some((_, rest)) => 1 + remaining(Cursor { input: rest }, extra).await,
+        None => extra(),
+    }
+}
+
+#[test]
+fn anonymous_lifetime_expansion_works() {
+    block_on(async move {
+        let input = [1, 2, 3, 4];
+        let mut cursor = Cursor { input: &input };
+        skip(&mut cursor, 1).await;
+        assert_eq!(cursor.input, &[2, 3, 4]);
+
+        let extra = 10;
+        assert_eq!(remaining(cursor, Box::new(|| extra)).await, 13);
+    });
+}
@@ -0,0 +1,159 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use async_recursion::{async_recursion, DepthExceeded};
+use futures_executor::block_on;
+
+#[derive(Debug, PartialEq)]
+enum WalkError {
+    TooDeep(usize),
+}
+
+impl From<DepthExceeded> for WalkError {
+    fn from(err: DepthExceeded) -> Self {
+        WalkError::TooDeep(err.max_depth())
+    }
+}
+
+#[async_recursion(max_depth = 10)]
+async fn countdown(n: u32) -> u32 {
+    if n == 0 {
+        0
+    } else {
+        1 + countdown(n - 1).await
+    }
+}
+
+#[async_recursion(max_depth = 10)]
+async fn try_countdown(n: u32) -> Result<u32, WalkError> {
+    if n == 0 {
+        Ok(0)
+    } else {
+        Ok(1 + try_countdown(n - 1).await?)
+    }
+}
+
+/// Returns `Pending` the first time it is polled, so that the caller can be moved elsewhere.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[async_recursion(max_depth = 10)]
+async fn yielding_countdown(n: u32) -> Result<u32, WalkError> {
+    YieldNow(false).await;
+    if n == 0 {
+        Ok(0)
+    } else {
+        Ok(1 + yielding_countdown(n - 1).await?)
+    }
+}
+
+#[async_recursion(max_depth = 3)]
+async fn outer(n: u32) -> Result<u32, WalkError> {
+    if n == 0 {
+        // Calls to another function don't count towards this function's depth
+        try_countdown(9).await
+    } else {
+        outer(n - 1).await
+    }
+}
+
+#[test]
+fn max_depth_allows_shallow_recursion() {
+    block_on(async move {
+        assert_eq!(countdown(9).await, 9);
+        assert_eq!(try_countdown(9).await, Ok(9));
+        assert_eq!(outer(2).await, Ok(9));
+    });
+}
+
+#[test]
+fn max_depth_returns_error() {
+    block_on(async move {
+        assert_eq!(try_countdown(10).await, Err(WalkError::TooDeep(10)));
+        assert_eq!(outer(3).await, Err(WalkError::TooDeep(3)));
+    });
+}
+
+#[test]
+#[should_panic(expected = "`countdown` exceeded the maximum recursion depth of 10")]
+fn max_depth_panics() {
+    block_on(countdown(10));
+}
+
+#[test]
+fn max_depth_is_per_top_level_call() {
+    block_on(async move {
+        for _ in 0..3 {
+            assert_eq!(try_countdown(9).await, Ok(9));
+        }
+
+        let (a, b) = join(yielding_countdown(9), yielding_countdown(9)).await;
+        assert_eq!((a, b), (Ok(9), Ok(9)));
+    });
+}
+
+#[test]
+fn max_depth_survives_moving_between_threads() {
+    for (n, expected) in [(9, Ok(9)), (10, Err(WalkError::TooDeep(10)))] {
+        let mut future = yielding_countdown(n);
+
+        // Poll the first few levels on this thread, then finish on other threads
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+
+        for _ in 0..3 {
+            future = std::thread::spawn(move || {
+                let mut cx = Context::from_waker(Waker::noop());
+                let _ = future.as_mut().poll(&mut cx);
+                future
+            })
+            .join()
+            .unwrap();
+        }
+
+        assert_eq!(block_on(future), expected);
+    }
+}
+
+/// Polls both futures on the same task, interleaving their frames.
+async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+    let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+    let (mut ra, mut rb) = (None, None);
+
+    std::future::poll_fn(|cx| {
+        if ra.is_none() {
+            if let Poll::Ready(out) = a.as_mut().poll(cx) {
+                ra = Some(out);
+            }
+        }
+        if rb.is_none() {
+            if let Poll::Ready(out) = b.as_mut().poll(cx) {
+                rb = Some(out);
+            }
+        }
+        if ra.is_some() && rb.is_some() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    (ra.unwrap(), rb.unwrap())
+}
@@ -0,0 +1,133 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+thread_local! {
+    // How many times the body of each function ran on this thread
+    static FIB_CALLS: Cell<usize> = const { Cell::new(0) };
+    static GLOBAL_CALLS: Cell<usize> = const { Cell::new(0) };
+    static RESOLVE_CALLS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count(calls: &'static std::thread::LocalKey<Cell<usize>>) {
+    calls.with(|calls| calls.set(calls.get() + 1));
+}
+
+fn take(calls: &'static std::thread::LocalKey<Cell<usize>>) -> usize {
+    calls.with(|calls| calls.replace(0))
+}
+
+#[async_recursion(memoize)]
+async fn fib(n: u64) -> u64 {
+    count(&FIB_CALLS);
+    match n {
+        0 | 1 => n,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[async_recursion(memoize(global))]
+async fn global_fib(n: u64) -> u64 {
+    count(&GLOBAL_CALLS);
+    match n {
+        0 | 1 => n,
+        _ => global_fib(n - 1).await + global_fib(n - 2).await,
+    }
+}
+
+/// Returns `Pending` the first time it is polled, so that other branches get to run meanwhile.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// Resolves the dependencies of a package, each of which depends on both packages below it.
+#[async_recursion(memoize)]
+async fn resolve(package: String, depth: u32) -> Vec<String> {
+    count(&RESOLVE_CALLS);
+    YieldNow(false).await;
+
+    let mut resolved = vec![package.clone()];
+    if depth > 0 {
+        let left = resolve(format!("{package}/{}", depth - 1), depth - 1);
+        let right = resolve(format!("{package}/{}", depth - 1), depth - 1);
+        let (left, right) = join(left, right).await;
+        assert_eq!(left, right);
+        resolved.extend(left);
+    }
+    resolved
+}
+
+#[test]
+fn memoize_computes_each_call_once() {
+    assert_eq!(block_on(fib(60)), 1548008755920);
+    assert_eq!(take(&FIB_CALLS), 61);
+}
+
+#[test]
+fn memoize_is_per_top_level_call() {
+    for _ in 0..3 {
+        assert_eq!(block_on(fib(20)), 6765);
+        assert_eq!(take(&FIB_CALLS), 21);
+    }
+}
+
+#[test]
+fn memoize_global_is_kept_across_calls() {
+    assert_eq!(block_on(global_fib(30)), 832040);
+    assert_eq!(take(&GLOBAL_CALLS), 31);
+    assert_eq!(block_on(global_fib(30)), 832040);
+    assert_eq!(block_on(global_fib(25)), 75025);
+    assert_eq!(take(&GLOBAL_CALLS), 0);
+}
+
+#[test]
+fn memoize_shares_calls_in_flight() {
+    let resolved = block_on(resolve("root".to_string(), 3));
+    assert_eq!(resolved, ["root", "root/2", "root/2/1", "root/2/1/0"]);
+    assert_eq!(take(&RESOLVE_CALLS), 4);
+}
+
+/// Polls both futures on the same task, interleaving their frames.
+async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+    let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+    let (mut ra, mut rb) = (None, None);
+
+    std::future::poll_fn(|cx| {
+        if ra.is_none() {
+            if let Poll::Ready(out) = a.as_mut().poll(cx) {
+                ra = Some(out);
+            }
+        }
+        if rb.is_none() {
+            if let Poll::Ready(out) = b.as_mut().poll(cx) {
+                rb = Some(out);
+            }
+        }
+        if ra.is_some() && rb.is_some() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    (ra.unwrap(), rb.unwrap())
+}
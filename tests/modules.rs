@@ -0,0 +1,90 @@
+use std::{future::Future, pin::Pin};
+
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+#[async_recursion]
+mod parser {
+    pub struct Cursor {
+        pub input: Vec<u8>,
+        pub pos: usize,
+    }
+
+    impl Cursor {
+        fn peek(&self) -> Option<u8> {
+            self.input.get(self.pos).copied()
+        }
+    }
+
+    pub async fn evaluate(input: &str) -> i64 {
+        let mut cursor = Cursor {
+            input: input.bytes().collect(),
+            pos: 0,
+        };
+        parse_expr(&mut cursor).await
+    }
+
+    pub async fn parse_expr(cursor: &mut Cursor) -> i64 {
+        let mut value = parse_term(cursor).await;
+        while cursor.peek() == Some(b'+') {
+            cursor.pos += 1;
+            value += parse_term(cursor).await;
+        }
+        value
+    }
+
+    async fn parse_term(cursor: &mut Cursor) -> i64 {
+        let mut value = parse_factor(cursor).await;
+        while cursor.peek() == Some(b'*') {
+            cursor.pos += 1;
+            value *= parse_factor(cursor).await;
+        }
+        value
+    }
+
+    async fn parse_factor(cursor: &mut Cursor) -> i64 {
+        match cursor.peek() {
+            Some(b'(') => {
+                cursor.pos += 1;
+                let value = parse_expr(cursor).await;
+                cursor.pos += 1;
+                value
+            }
+            Some(digit) => {
+                cursor.pos += 1;
+                i64::from(digit - b'0')
+            }
+            None => 0,
+        }
+    }
+
+    pub async fn countdown(n: u32) -> u32 {
+        if n == 0 {
+            0
+        } else {
+            1 + countdown(n - 1).await
+        }
+    }
+}
+
+#[test]
+fn module_cycles_are_broken() {
+    block_on(async move {
+        assert_eq!(parser::evaluate("1+2*3").await, 7);
+        assert_eq!(parser::evaluate("(1+2)*3").await, 9);
+        assert_eq!(parser::evaluate("2*(3+(4*5))+1").await, 47);
+        assert_eq!(parser::countdown(10).await, 10);
+    });
+}
+
+#[test]
+fn module_functions_are_boxed() {
+    fn assert_is_boxed<'a, T>(_: Pin<Box<dyn Future<Output = T> + Send + 'a>>) {}
+
+    let mut cursor = parser::Cursor {
+        input: b"1".to_vec(),
+        pos: 0,
+    };
+    assert_is_boxed(parser::parse_expr(&mut cursor));
+    assert_is_boxed(parser::countdown(1));
+}
@@ -0,0 +1,16 @@
+#![no_std]
+
+use async_recursion::async_recursion;
+
+#[async_recursion]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => 1,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[test]
+fn no_std_works() {
+    assert_eq!(futures_executor::block_on(fib(10)), 89);
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::{future::Future, rc::Rc, sync::Arc};
+
+use async_recursion::{async_recursion, Pooled};
+use futures_executor::block_on;
+
+#[async_recursion(pool)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => n as u64,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[async_recursion(pool)]
+async fn hold(n: u32, token: Arc<()>) {
+    if n == 0 {
+        std::future::pending::<()>().await;
+    } else {
+        hold(n - 1, token.clone()).await;
+    }
+}
+
+#[async_recursion(?Send, pool)]
+async fn rc_sum(n: Rc<u64>) -> u64 {
+    if *n == 0 {
+        0
+    } else {
+        *n + rc_sum(Rc::new(*n - 1)).await
+    }
+}
+
+#[async_recursion(Sync, pool)]
+async fn sync_sum(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        n + sync_sum(n - 1).await
+    }
+}
+
+#[async_recursion(pool)]
+async fn contains(items: &[u32], item: &u32) -> bool {
+    match items.split_first() {
+        Some((first, rest)) => first == item || contains(rest, item).await,
+        None => false,
+    }
+}
+
+#[async_recursion(pool, max_depth = 10)]
+async fn countdown(n: u32) -> u32 {
+    if n == 0 {
+        0
+    } else {
+        1 + countdown(n - 1).await
+    }
+}
+
+#[test]
+fn pool_recursion() {
+    assert_eq!(block_on(fib(20)), 6765);
+    assert_eq!(block_on(countdown(9)), 9);
+}
+
+#[test]
+fn pool_borrowed_arguments() {
+    let items = vec![1, 2, 3, 4];
+    assert!(block_on(contains(&items, &3)));
+    assert!(!block_on(contains(&items, &5)));
+}
+
+#[test]
+fn pool_reuses_blocks() {
+    fn address(future: &mut Pooled<dyn Future<Output = u64> + Send>) -> *const () {
+        &*future.as_mut() as *const _ as *const ()
+    }
+
+    let mut first = fib(10);
+    let first_address = address(&mut first);
+    drop(first);
+
+    let mut second = fib(10);
+    assert_eq!(address(&mut second), first_address);
+    assert_eq!(block_on(second), 55);
+}
+
+fn assert_is_send(_: impl Send) {}
+
+fn assert_is_send_and_sync(_: impl Send + Sync) {}
+
+#[test]
+fn pool_bounds() {
+    assert_is_send(fib(10));
+    assert_is_send_and_sync(sync_sum(10));
+    assert_eq!(block_on(rc_sum(Rc::new(10))), 55);
+}
+
+#[test]
+fn pool_drops_pending_calls() {
+    common::assert_drops_pending_calls(100, hold);
+}
+
+#[test]
+fn pool_moves_between_threads() {
+    let future = std::thread::spawn(|| fib(15)).join().unwrap();
+    assert_eq!(block_on(future), 610);
+}
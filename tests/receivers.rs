@@ -0,0 +1,84 @@
+use std::{pin::Pin, sync::Arc};
+
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+struct Actor {
+    base: u32,
+}
+
+impl Actor {
+    #[async_recursion]
+    async fn arc(self: Arc<Self>, n: u32) -> u32 {
+        if n == 0 {
+            self.base
+        } else {
+            1 + self.clone().arc(n - 1).await
+        }
+    }
+
+    #[async_recursion]
+    async fn ref_arc(self: &Arc<Self>, n: u32) -> u32 {
+        if n == 0 {
+            self.base
+        } else {
+            1 + self.ref_arc(n - 1).await
+        }
+    }
+
+    #[async_recursion]
+    async fn boxed(self: Box<Self>, n: u32) -> u32 {
+        if n == 0 {
+            self.base
+        } else {
+            1 + self.boxed(n - 1).await
+        }
+    }
+
+    #[allow(clippy::needless_arbitrary_self_type)]
+    #[async_recursion]
+    async fn typed_ref(self: &Self, n: u32) -> u32 {
+        if n == 0 {
+            self.base
+        } else {
+            1 + self.typed_ref(n - 1).await
+        }
+    }
+
+    #[async_recursion]
+    async fn pinned(self: Pin<&mut Self>, n: u32) -> u32 {
+        let this = self.get_mut();
+        if n == 0 {
+            this.base
+        } else {
+            this.base += 1;
+            Pin::new(this).pinned(n - 1).await
+        }
+    }
+}
+
+#[test]
+fn typed_receivers_work() {
+    block_on(async move {
+        assert_eq!(Arc::new(Actor { base: 1 }).arc(5).await, 6);
+        assert_eq!(Arc::new(Actor { base: 2 }).ref_arc(5).await, 7);
+        assert_eq!(Box::new(Actor { base: 3 }).boxed(5).await, 8);
+        assert_eq!(Actor { base: 4 }.typed_ref(5).await, 9);
+
+        let mut actor = Actor { base: 5 };
+        assert_eq!(Pin::new(&mut actor).pinned(5).await, 10);
+        assert_eq!(actor.base, 10);
+    });
+}
+
+#[test]
+fn typed_receivers_are_send() {
+    fn assert_is_send(_: impl Send) {}
+
+    let actor = Arc::new(Actor { base: 0 });
+    assert_is_send(actor.ref_arc(1));
+    assert_is_send(actor.arc(1));
+
+    let mut actor = Actor { base: 0 };
+    assert_is_send(Pin::new(&mut actor).pinned(1));
+}
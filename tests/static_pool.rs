@@ -0,0 +1,105 @@
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use async_recursion::{async_recursion, PoolExhausted};
+use futures_executor::block_on;
+
+/// The slots are shared by every thread, so tests calling the same functions mustn't run at once.
+fn exclusive() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Unbalanced,
+    TooDeep,
+}
+
+impl From<PoolExhausted> for Error {
+    fn from(err: PoolExhausted) -> Self {
+        assert_eq!(err.capacity(), 4);
+        Error::TooDeep
+    }
+}
+
+/// Returns how deeply nested the brackets at the start of `input` are, and how many bytes they
+/// take up.
+#[async_recursion(static_pool = 4)]
+async fn nesting(input: &[u8]) -> Result<(usize, usize), Error> {
+    if input.first() != Some(&b'[') {
+        return Ok((0, 0));
+    }
+
+    let (mut depth, mut len) = (0, 1);
+    while input.get(len) == Some(&b'[') {
+        let (inner, inner_len) = nesting(&input[len..]).await?;
+        depth = depth.max(inner);
+        len += inner_len;
+    }
+    match input.get(len) {
+        Some(b']') => Ok((depth + 1, len + 1)),
+        _ => Err(Error::Unbalanced),
+    }
+}
+
+#[async_recursion(static_pool = 8)]
+async fn countdown(mut n: u32) -> u32 {
+    if n > 0 {
+        n -= 1;
+        1 + countdown(n).await
+    } else {
+        0
+    }
+}
+
+#[async_recursion(Sync, static_pool = 8)]
+async fn sum(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        n + sum(n - 1).await
+    }
+}
+
+#[test]
+fn static_pool_recursion() {
+    let _guard = exclusive();
+    assert_eq!(block_on(nesting(b"[[][[]]]x")), Ok((3, 8)));
+    assert_eq!(block_on(nesting(b"[[]")), Err(Error::Unbalanced));
+    assert_eq!(block_on(countdown(7)), 7);
+}
+
+#[test]
+fn static_pool_exhausted_error() {
+    let _guard = exclusive();
+    assert_eq!(block_on(nesting(b"[[[[]]]]")), Ok((4, 8)));
+    assert_eq!(block_on(nesting(b"[[[[[]]]]]")), Err(Error::TooDeep));
+}
+
+#[test]
+#[should_panic(expected = "`countdown` used all 8 slots of its static pool")]
+fn static_pool_exhausted_panic() {
+    let _guard = exclusive();
+    block_on(countdown(8));
+}
+
+#[test]
+fn static_pool_frees_slots() {
+    let _guard = exclusive();
+    for _ in 0..3 {
+        assert_eq!(block_on(countdown(7)), 7);
+    }
+
+    // Dropping a future frees its slot without it having been polled
+    let futures: Vec<_> = (0..8).map(countdown).collect();
+    drop(futures);
+    assert_eq!(block_on(countdown(7)), 7);
+}
+
+fn assert_is_send_and_sync(_: impl Send + Sync) {}
+
+#[test]
+fn static_pool_bounds() {
+    assert_is_send_and_sync(sum(5));
+    assert_eq!(block_on(sum(5)), 15);
+}
@@ -23,12 +23,12 @@ impl Empty {
         }
     }
 
-    #[async_recursion]
+    #[async_recursion(allow_nonrecursive)]
     pub async fn generic_parameter<T>(&self, _something: &T) -> u64 {
         0
     }
 
-    #[async_recursion]
+    #[async_recursion(allow_nonrecursive)]
     pub async fn all_of_the_above<'a, 'b, S, T>(
         &self,
         // Some references with / without lifetimes to generic parameters
@@ -74,3 +74,52 @@ fn struct_method_with_generic_parameter_works() {
         );
     })
 }
+
+pub struct Parser<'a, T> {
+    input: &'a [T],
+}
+
+impl<'a, T: PartialEq + Sync> Parser<'a, T> {
+    #[async_recursion]
+    pub async fn count_by_value(self, needle: &T) -> usize {
+        match self.input.split_first() {
+            None => 0,
+            Some((head, rest)) => {
+                let found = usize::from(head == needle);
+                found + Parser { input: rest }.count_by_value(needle).await
+            }
+        }
+    }
+
+    #[async_recursion]
+    pub async fn count_by_ref(&self, needle: &T) -> usize {
+        match self.input.split_first() {
+            None => 0,
+            Some((head, rest)) => {
+                let found = usize::from(head == needle);
+                found + Parser { input: rest }.count_by_ref(needle).await
+            }
+        }
+    }
+
+    #[async_recursion]
+    pub async fn count_boxed(self: Box<Self>, needle: &T) -> usize {
+        match self.input.split_first() {
+            None => 0,
+            Some((head, rest)) => {
+                let found = usize::from(head == needle);
+                found + Box::new(Parser { input: rest }).count_boxed(needle).await
+            }
+        }
+    }
+}
+
+#[test]
+fn struct_method_with_impl_generics_works() {
+    block_on(async move {
+        let input = [1, 2, 1, 3, 1];
+        assert_eq!(Parser { input: &input }.count_by_value(&1).await, 3);
+        assert_eq!(Parser { input: &input }.count_by_ref(&3).await, 1);
+        assert_eq!(Box::new(Parser { input: &input }).count_boxed(&2).await, 1);
+    });
+}
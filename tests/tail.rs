@@ -0,0 +1,96 @@
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+#[async_recursion(tail)]
+async fn sum(n: u64, acc: u64) -> u64 {
+    if n == 0 {
+        return acc;
+    }
+
+    sum(n - 1, acc + n).await
+}
+
+#[async_recursion(tail)]
+async fn collatz_steps(mut n: u64, steps: u32) -> u32 {
+    if n == 1 {
+        return steps;
+    }
+
+    n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+    return collatz_steps(n, steps + 1).await;
+}
+
+#[async_recursion(tail)]
+async fn find(haystack: &[u32], needle: u32, index: usize) -> Option<usize> {
+    match haystack.get(index) {
+        None => None,
+        Some(&value) if value == needle => Some(index),
+        Some(_) => find(haystack, needle, index + 1).await,
+    }
+}
+
+#[async_recursion(tail)]
+async fn drain(items: Vec<u32>, out: &mut Vec<u32>) {
+    let mut items = items;
+    if let Some(item) = items.pop() {
+        out.push(item);
+        drain(items, out).await;
+    }
+}
+
+struct Graph {
+    next: Vec<usize>,
+}
+
+#[async_recursion(tail)]
+impl Graph {
+    async fn last(&self, node: usize) -> usize {
+        if self.next[node] == node {
+            node
+        } else {
+            self.last(self.next[node]).await
+        }
+    }
+
+    async fn distance(&self, node: usize, steps: usize) -> usize {
+        if self.next[node] == node {
+            steps
+        } else {
+            Self::distance(self, self.next[node], steps + 1).await
+        }
+    }
+}
+
+#[test]
+fn tail_calls_run_in_constant_space() {
+    // Deep enough to overflow the stack if each call was polled by its caller
+    let n = 1_000_000;
+    let total = std::thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(move || block_on(sum(n, 0)))
+        .unwrap()
+        .join()
+        .unwrap();
+    assert_eq!(total, n * (n + 1) / 2);
+}
+
+#[test]
+fn tail_calls() {
+    block_on(async move {
+        assert_eq!(collatz_steps(27, 0).await, 111);
+
+        let haystack = [4, 8, 15, 16, 23, 42];
+        assert_eq!(find(&haystack, 23, 0).await, Some(4));
+        assert_eq!(find(&haystack, 7, 0).await, None);
+
+        let mut out = vec![];
+        drain(vec![1, 2, 3], &mut out).await;
+        assert_eq!(out, vec![3, 2, 1]);
+
+        let graph = Graph {
+            next: vec![1, 2, 3, 3],
+        };
+        assert_eq!(graph.last(0).await, 3);
+        assert_eq!(graph.distance(0, 0).await, 3);
+    });
+}
@@ -0,0 +1,128 @@
+use async_recursion::async_recursion;
+use futures_executor::block_on;
+
+trait Node: Send + Sync {
+    fn value(&self) -> u64;
+
+    fn children(&self) -> &[Box<dyn Node>];
+}
+
+struct Leaf(u64);
+
+impl Node for Leaf {
+    fn value(&self) -> u64 {
+        self.0
+    }
+
+    fn children(&self) -> &[Box<dyn Node>] {
+        &[]
+    }
+}
+
+struct Branch(u64, Vec<Box<dyn Node>>);
+
+impl Node for Branch {
+    fn value(&self) -> u64 {
+        self.0
+    }
+
+    fn children(&self) -> &[Box<dyn Node>] {
+        &self.1
+    }
+}
+
+#[async_recursion]
+trait Visitor: Send + Sync {
+    async fn visit(&self, node: &dyn Node) -> u64;
+
+    async fn visit_children(&self, node: &dyn Node) -> u64 {
+        let mut total = 0;
+        for child in node.children() {
+            total += self.visit(child.as_ref()).await;
+        }
+        total
+    }
+}
+
+struct Sum;
+
+#[async_recursion]
+impl Visitor for Sum {
+    async fn visit(&self, node: &dyn Node) -> u64 {
+        node.value() + self.visit_children(node).await
+    }
+}
+
+struct CountNodes;
+
+#[async_recursion]
+impl Visitor for CountNodes {
+    async fn visit(&self, node: &dyn Node) -> u64 {
+        1 + self.visit_children(node).await
+    }
+
+    async fn visit_children(&self, node: &dyn Node) -> u64 {
+        let mut total = 0;
+        for child in node.children().iter().rev() {
+            total += self.visit(child.as_ref()).await;
+        }
+        total
+    }
+}
+
+trait Countdown {
+    #[async_recursion(?Send)]
+    async fn countdown(&self, n: u32) -> u32;
+}
+
+struct Counter;
+
+impl Countdown for Counter {
+    #[async_recursion(?Send)]
+    async fn countdown(&self, n: u32) -> u32 {
+        if n == 0 {
+            0
+        } else {
+            1 + self.countdown(n - 1).await
+        }
+    }
+}
+
+fn tree() -> Branch {
+    Branch(
+        1,
+        vec![
+            Box::new(Leaf(2)),
+            Box::new(Branch(3, vec![Box::new(Leaf(4)), Box::new(Leaf(5))])),
+        ],
+    )
+}
+
+#[test]
+fn trait_objects_can_recurse() {
+    block_on(async move {
+        let tree = tree();
+        let visitors: Vec<Box<dyn Visitor>> = vec![Box::new(Sum), Box::new(CountNodes)];
+
+        assert_eq!(visitors[0].visit(&tree).await, 15);
+        assert_eq!(visitors[1].visit(&tree).await, 5);
+    });
+}
+
+#[test]
+fn trait_methods_are_send() {
+    fn assert_is_send(_: impl Send) {}
+
+    let tree = tree();
+    let visitor: Box<dyn Visitor> = Box::new(Sum);
+    assert_is_send(visitor.visit(&tree));
+    assert_is_send(visitor.visit_children(&tree));
+}
+
+#[test]
+fn trait_method_declarations_work() {
+    block_on(async move {
+        let counter: Box<dyn Countdown> = Box::new(Counter);
+        assert_eq!(counter.countdown(10).await, 10);
+    });
+}
@@ -0,0 +1,225 @@
+mod common;
+
+use std::{
+    future::Future,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Waker},
+};
+
+use async_recursion::{async_recursion, DepthExceeded};
+use common::{with_small_stack, yield_now};
+use futures_executor::block_on;
+
+#[async_recursion(trampoline)]
+async fn sum(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        n + sum(n - 1).await
+    }
+}
+
+#[async_recursion(trampoline)]
+async fn yielding_sum(n: u64) -> u64 {
+    yield_now().await;
+    if n == 0 {
+        0
+    } else {
+        let rest = yielding_sum(n - 1).await;
+        yield_now().await;
+        n + rest
+    }
+}
+
+#[async_recursion(trampoline)]
+async fn tree_sum(depth: u32) -> u64 {
+    if depth == 0 {
+        yield_now().await;
+        1
+    } else {
+        let (a, b) = join(tree_sum(depth - 1), tree_sum(depth - 1)).await;
+        a + b
+    }
+}
+
+#[async_recursion(trampoline)]
+mod parity {
+    pub async fn is_even(n: u64) -> bool {
+        if n == 0 {
+            true
+        } else {
+            is_odd(n - 1).await
+        }
+    }
+
+    pub async fn is_odd(n: u64) -> bool {
+        if n == 0 {
+            false
+        } else {
+            is_even(n - 1).await
+        }
+    }
+}
+
+#[async_recursion(?Send, trampoline)]
+async fn rc_sum(n: Rc<u64>) -> u64 {
+    if *n == 0 {
+        0
+    } else {
+        *n + rc_sum(Rc::new(*n - 1)).await
+    }
+}
+
+#[async_recursion(Sync, trampoline)]
+async fn sync_sum(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        n + sync_sum(n - 1).await
+    }
+}
+
+#[async_recursion(trampoline, max_depth = 10)]
+async fn try_countdown(n: u32) -> Result<u32, DepthExceeded> {
+    if n == 0 {
+        Ok(0)
+    } else {
+        Ok(1 + try_countdown(n - 1).await?)
+    }
+}
+
+#[async_recursion(trampoline)]
+async fn hold(n: u32, token: Arc<()>) {
+    if n == 0 {
+        std::future::pending::<()>().await;
+    } else {
+        hold(n - 1, token.clone()).await;
+    }
+}
+
+#[async_recursion(trampoline)]
+async fn count_polls(n: u32, polls: Arc<AtomicUsize>) -> usize {
+    if n == 0 {
+        // Never completes, counting each time it is polled
+        std::future::poll_fn(|cx| {
+            polls.fetch_add(1, Ordering::Relaxed);
+            cx.waker().wake_by_ref();
+            Poll::<()>::Pending
+        })
+        .await;
+        unreachable!()
+    } else {
+        // Poll the call once, as `select!` does with a branch that loses, then leave it pending
+        let mut call = count_polls(n - 1, polls.clone());
+        let _ = std::future::poll_fn(|cx| Poll::Ready(call.as_mut().poll(cx))).await;
+        for _ in 0..10 {
+            yield_now().await;
+        }
+        polls.load(Ordering::Relaxed)
+    }
+}
+
+#[test]
+fn trampoline_deep_recursion() {
+    let n = 100_000;
+    assert_eq!(with_small_stack(move || block_on(sum(n))), n * (n + 1) / 2);
+}
+
+#[test]
+fn trampoline_deep_recursion_with_wakeups() {
+    let n = 1_000;
+    assert_eq!(
+        with_small_stack(move || block_on(yielding_sum(n))),
+        n * (n + 1) / 2
+    );
+}
+
+#[test]
+fn trampoline_joined_calls() {
+    assert_eq!(block_on(tree_sum(10)), 1024);
+}
+
+#[test]
+fn trampoline_mutual_recursion() {
+    assert!(with_small_stack(|| block_on(parity::is_even(100_000))));
+    assert!(block_on(parity::is_odd(7)));
+}
+
+fn assert_is_send(_: impl Send) {}
+
+fn assert_is_send_and_sync(_: impl Send + Sync) {}
+
+#[test]
+fn trampoline_bounds() {
+    assert_is_send(sum(10));
+    assert_is_send_and_sync(sync_sum(10));
+    assert_eq!(block_on(rc_sum(Rc::new(10))), 55);
+}
+
+#[test]
+fn trampoline_with_max_depth() {
+    block_on(async move {
+        assert_eq!(try_countdown(9).await, Ok(9));
+        assert_eq!(try_countdown(10).await.unwrap_err().max_depth(), 10);
+    });
+}
+
+#[test]
+fn trampoline_only_polls_calls_when_their_caller_does() {
+    assert_eq!(block_on(count_polls(3, Arc::default())), 1);
+}
+
+#[test]
+fn trampoline_survives_moving_between_threads() {
+    let mut future = yielding_sum(100);
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+
+    for _ in 0..3 {
+        future = std::thread::spawn(move || {
+            let mut cx = Context::from_waker(Waker::noop());
+            let _ = future.as_mut().poll(&mut cx);
+            future
+        })
+        .join()
+        .unwrap();
+    }
+
+    assert_eq!(block_on(future), 5050);
+}
+
+#[test]
+fn trampoline_drops_pending_calls() {
+    common::assert_drops_pending_calls(1_000, hold);
+}
+
+/// Polls both futures on the same task.
+async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+    let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+    let (mut ra, mut rb) = (None, None);
+
+    std::future::poll_fn(|cx| {
+        if ra.is_none() {
+            if let Poll::Ready(out) = a.as_mut().poll(cx) {
+                ra = Some(out);
+            }
+        }
+        if rb.is_none() {
+            if let Poll::Ready(out) = b.as_mut().poll(cx) {
+                rb = Some(out);
+            }
+        }
+        if ra.is_some() && rb.is_some() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    (ra.unwrap(), rb.unwrap())
+}
@@ -0,0 +1,18 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(call_site, boxer = async_recursion::BoxPin)]
+async fn call_site() {}
+
+#[async_recursion(pool, boxer = async_recursion::BoxPin)]
+async fn pooled() {}
+
+struct NotABoxer;
+
+#[async_recursion(boxer = NotABoxer)]
+async fn not_a_boxer(n: u32) {
+    if n > 0 {
+        not_a_boxer(n - 1).await
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(call_site, ?Send)]
+async fn not_send() {}
+
+#[async_recursion(Sync, call_site)]
+async fn sync() {}
+
+#[async_recursion(call_site, call_site)]
+async fn repeated() {}
+
+fn main() {}
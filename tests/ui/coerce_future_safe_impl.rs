@@ -0,0 +1,11 @@
+use async_recursion::CoerceFuture;
+
+struct NotAFuture;
+
+impl<G> CoerceFuture<G> for NotAFuture {
+    fn coerce(_: *mut G) -> *mut Self {
+        std::ptr::null_mut()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(crate)]
+async fn missing_path() {}
+
+#[async_recursion(crate = "async_recursion")]
+async fn string_path() {}
+
+#[async_recursion(crate = async_recursion, crate = async_recursion)]
+async fn repeated() {}
+
+fn main() {}
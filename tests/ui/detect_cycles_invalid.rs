@@ -0,0 +1,23 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(detect_cycles(node))]
+async fn without_key(node: u32) -> Result<u32, async_recursion::CycleDetected<u32>> {
+    if node == 0 { Ok(0) } else { without_key(node - 1).await }
+}
+
+#[async_recursion(detect_cycles(key = node))]
+async fn infallible(node: u32) -> u32 {
+    if node == 0 { 0 } else { infallible(node - 1).await }
+}
+
+#[async_recursion(detect_cycles(key = node), tail)]
+async fn tail_calls(node: u32) -> Result<u32, async_recursion::CycleDetected<u32>> {
+    if node == 0 { Ok(0) } else { tail_calls(node - 1).await }
+}
+
+#[async_recursion(detect_cycles(key = node))]
+async fn wrong_error(node: u32) -> Result<u32, String> {
+    if node == 0 { Ok(0) } else { wrong_error(node - 1).await }
+}
+
+fn main() {}
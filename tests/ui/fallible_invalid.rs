@@ -0,0 +1,16 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(fallible)]
+async fn infallible(n: u32) -> u32 {
+    if n == 0 { 0 } else { infallible(n - 1).await }
+}
+
+#[async_recursion(fallible)]
+async fn wrong_error(n: u32) -> Result<u32, String> {
+    if n == 0 { Ok(0) } else { wrong_error(n - 1).await }
+}
+
+#[async_recursion(pool, fallible)]
+async fn pooled() {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use async_recursion::async_recursion;
+
+struct S;
+
+#[async_recursion]
+impl S {
+    fn not_async(&self) {}
+}
+
+struct T;
+
+#[async_recursion]
+impl T {
+    async fn is_async(&self) {}
+
+    #[async_recursion(?Send)]
+    fn not_async(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(inline = 0)]
+async fn empty() {}
+
+#[async_recursion(call_site, inline = 64)]
+async fn call_site() {}
+
+#[async_recursion(pool, inline = 64)]
+async fn pooled() {}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(max_depth = 0)]
+async fn zero() {}
+
+#[async_recursion(max_depth = -1)]
+async fn negative() {}
+
+#[async_recursion(max_depth)]
+async fn missing() {}
+
+#[async_recursion(call_site, max_depth = 10)]
+async fn call_site() {}
+
+fn main() {}
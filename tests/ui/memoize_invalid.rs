@@ -0,0 +1,42 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(memoize(forever))]
+async fn forever(n: u32) -> u32 {
+    if n == 0 { 0 } else { forever(n - 1).await }
+}
+
+#[async_recursion(memoize, pool)]
+async fn pooled(n: u32) -> u32 {
+    if n == 0 { 0 } else { pooled(n - 1).await }
+}
+
+#[async_recursion(memoize)]
+async fn borrowed(s: &str) -> usize {
+    if s.is_empty() { 0 } else { 1 + borrowed(&s[1..]).await }
+}
+
+#[async_recursion(memoize)]
+async fn generic<T: Clone>(value: T, n: u32) -> T {
+    if n == 0 { value } else { generic(value, n - 1).await }
+}
+
+#[async_recursion(memoize)]
+async fn destructured((a, b): (u32, u32)) -> u32 {
+    if a == 0 { b } else { destructured((a - 1, b)).await }
+}
+
+#[async_recursion(memoize)]
+async fn unhashable(n: f64) -> f64 {
+    if n <= 0.0 { 0.0 } else { unhashable(n - 1.0).await }
+}
+
+struct Tree;
+
+impl Tree {
+    #[async_recursion(memoize)]
+    async fn depth(&self, n: u32) -> u32 {
+        if n == 0 { 0 } else { self.depth(n - 1).await }
+    }
+}
+
+fn main() {}
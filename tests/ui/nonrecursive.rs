@@ -0,0 +1,82 @@
+#![deny(deprecated)]
+
+use async_recursion::async_recursion;
+
+#[async_recursion]
+async fn not_recursive(n: u32) -> u32 {
+    n + 1
+}
+
+#[async_recursion(allow_nonrecursive)]
+async fn allowed(n: u32) -> u32 {
+    n + 1
+}
+
+#[async_recursion]
+async fn recursive(n: u32) -> u32 {
+    if n == 0 { 0 } else { recursive(n - 1).await }
+}
+
+#[async_recursion]
+async fn is_even(n: u32) -> bool {
+    if n == 0 { true } else { is_odd(n - 1).await }
+}
+
+async fn is_odd(n: u32) -> bool {
+    if n == 0 { false } else { is_even(n - 1).await }
+}
+
+struct List(Option<Box<List>>);
+
+#[async_recursion]
+impl List {
+    async fn len(&self) -> usize {
+        match &self.0 {
+            Some(next) => 1 + next.len().await,
+            None => 1,
+        }
+    }
+
+    async fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+#[async_recursion]
+trait Visitor: Sync {
+    async fn visit(&self, list: &List) -> usize;
+
+    async fn visit_next(&self, list: &List) -> usize {
+        match &list.0 {
+            Some(next) => self.visit(next).await,
+            None => 0,
+        }
+    }
+}
+
+struct Counter;
+
+#[async_recursion]
+impl Visitor for Counter {
+    async fn visit(&self, list: &List) -> usize {
+        1 + self.visit_next(list).await
+    }
+}
+
+#[async_recursion]
+trait Named {
+    async fn name(&self) -> String {
+        String::from("unnamed")
+    }
+}
+
+struct Zero;
+
+#[async_recursion]
+impl Visitor for Zero {
+    async fn visit(&self, _list: &List) -> usize {
+        0
+    }
+}
+
+fn main() {}
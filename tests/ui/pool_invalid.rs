@@ -0,0 +1,6 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(call_site, pool)]
+async fn call_site() {}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(static_pool = 0)]
+async fn empty() {}
+
+#[async_recursion(pool, static_pool = 4)]
+async fn pooled() {}
+
+#[async_recursion(static_pool = 4)]
+async fn generic<T: Send>(x: T) -> T {
+    generic(x).await
+}
+
+#[async_recursion(static_pool = 4)]
+async fn pattern((a, b): (u8, u8)) -> u8 {
+    pattern((b, a)).await
+}
+
+struct Parser;
+
+impl Parser {
+    #[async_recursion(static_pool = 4)]
+    async fn method(&self) {
+        self.method().await
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(tail)]
+async fn not_in_tail_position(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        n + not_in_tail_position(n - 1).await
+    }
+}
+
+#[async_recursion(tail)]
+async fn pattern_argument((a, b): (u64, u64)) -> u64 {
+    if a == 0 {
+        b
+    } else {
+        pattern_argument((a - 1, b + 1)).await
+    }
+}
+
+#[async_recursion(call_site, tail)]
+async fn call_site() {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(call_site, trampoline)]
+async fn call_site() {}
+
+#[async_recursion(trampoline)]
+async fn borrowed(n: &u32) -> u32 {
+    if *n == 0 { 0 } else { borrowed(&(n - 1)).await }
+}
+
+struct Node;
+
+impl Node {
+    #[async_recursion(trampoline)]
+    async fn method(&self) {
+        self.method().await
+    }
+}
+
+fn main() {}
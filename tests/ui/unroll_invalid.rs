@@ -0,0 +1,48 @@
+use async_recursion::async_recursion;
+
+#[async_recursion(no_alloc)]
+async fn without_unroll(n: u32) -> u32 {
+    if n == 0 { 0 } else { without_unroll(n - 1).await }
+}
+
+#[async_recursion(unroll = 0)]
+async fn no_levels(n: u32) -> u32 {
+    if n == 0 { 0 } else { no_levels(n - 1).await }
+}
+
+#[async_recursion(?Send, unroll = 2)]
+async fn not_send(n: u32) -> u32 {
+    if n == 0 { 0 } else { not_send(n - 1).await }
+}
+
+#[async_recursion(unroll = 2, pool)]
+async fn pooled(n: u32) -> u32 {
+    if n == 0 { 0 } else { pooled(n - 1).await }
+}
+
+#[async_recursion(unroll = 2)]
+async fn generic<T: Clone>(value: T, n: u32) -> T {
+    if n == 0 { value } else { generic(value, n - 1).await }
+}
+
+struct Tree;
+
+impl Tree {
+    #[async_recursion(unroll = 2)]
+    async fn depth(&self, n: u32) -> u32 {
+        if n == 0 { 0 } else { self.depth(n - 1).await }
+    }
+}
+
+#[async_recursion(unroll = 2)]
+mod parity {
+    pub async fn is_even(n: u32) -> bool {
+        if n == 0 { true } else { is_odd(n - 1).await }
+    }
+
+    pub async fn is_odd(n: u32) -> bool {
+        if n == 0 { false } else { is_even(n - 1).await }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,128 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use async_recursion::{async_recursion, DepthExceeded};
+
+/// Counts the allocations made by the current thread.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Runs `future` to completion, returning its output and the number of allocations it made.
+fn run<F: Future>(future: F) -> (F::Output, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    let out = loop {
+        if let Poll::Ready(out) = future.as_mut().poll(&mut cx) {
+            break out;
+        }
+    };
+    (out, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[derive(Debug, PartialEq)]
+enum WalkError {
+    TooDeep(usize),
+}
+
+impl From<DepthExceeded> for WalkError {
+    fn from(err: DepthExceeded) -> Self {
+        WalkError::TooDeep(err.max_depth())
+    }
+}
+
+#[async_recursion(unroll = 4)]
+async fn countdown(n: u32) -> u32 {
+    if n == 0 {
+        0
+    } else {
+        1 + countdown(n - 1).await
+    }
+}
+
+#[async_recursion(unroll = 3)]
+async fn fib(n: u32) -> u64 {
+    match n {
+        0 | 1 => n as u64,
+        _ => fib(n - 1).await + fib(n - 2).await,
+    }
+}
+
+#[async_recursion(unroll = 2)]
+async fn sum(values: &[u64]) -> u64 {
+    match values {
+        [] => 0,
+        [first, rest @ ..] => first + sum(rest).await,
+    }
+}
+
+#[async_recursion(unroll = 4, no_alloc)]
+async fn try_countdown(n: u32) -> Result<u32, WalkError> {
+    if n == 0 {
+        Ok(0)
+    } else {
+        Ok(1 + try_countdown(n - 1).await?)
+    }
+}
+
+#[async_recursion(unroll = 4, no_alloc)]
+async fn countdown_or_panic(n: u32) -> u32 {
+    if n == 0 {
+        0
+    } else {
+        1 + countdown_or_panic(n - 1).await
+    }
+}
+
+#[test]
+fn unroll_computes_the_same_results() {
+    for n in 0..20 {
+        assert_eq!(run(countdown(n)).0, n);
+    }
+    assert_eq!(run(fib(20)).0, 6765);
+    assert_eq!(run(sum(&[1, 2, 3, 4, 5])).0, 15);
+}
+
+#[test]
+fn unroll_allocates_once_per_unrolled_levels() {
+    assert_eq!(run(countdown(0)), (0, 0));
+    assert_eq!(run(countdown(3)), (3, 0));
+    assert_eq!(run(countdown(4)), (4, 1));
+    assert_eq!(run(countdown(7)), (7, 1));
+    assert_eq!(run(countdown(8)), (8, 2));
+}
+
+#[test]
+fn no_alloc_never_allocates() {
+    assert_eq!(run(try_countdown(3)), (Ok(3), 0));
+    assert_eq!(run(try_countdown(4)), (Err(WalkError::TooDeep(4)), 0));
+    assert_eq!(run(countdown_or_panic(3)), (3, 0));
+}
+
+#[test]
+#[should_panic(expected = "`countdown_or_panic` exceeded the maximum recursion depth of 4")]
+fn no_alloc_panics() {
+    run(countdown_or_panic(4));
+}